@@ -1,44 +1,115 @@
+use monitor::Rect;
 use serde::{Deserialize, Serialize};
 
+#[cfg(target_os = "linux")]
+mod linux;
+
 #[cfg(target_os = "macos")]
 mod macos;
 
-#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+mod system_bar;
+mod tray;
+mod visibility;
+
+#[cfg(target_os = "linux")]
+pub use linux::status_notifier::{
+    MenuItem, MenuItemKind, Pixmap, ScrollOrientation, Status, StatusNotifierConfig,
+    StatusNotifierError, StatusNotifierItem, ToolTip, TrayEvent,
+};
+pub use system_bar::{get_system_bars, Edge, SystemBar, SystemBarKind};
+pub use tray::{get_status_item, set_activation_position, TrayGeometry, TrayIconExt};
+pub use visibility::{
+    get_menubar_visibility, is_menubar_visible, watch_menubar_visibility, MenubarVisibility,
+    VisibilityChange, VisibilityEvent, VisibilityStream,
+};
+
+/// The menubar of a monitor. Geometry is in the coordinate space windows are positioned
+/// in: a top-left origin, logical points on macOS and physical pixels elsewhere.
+#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Menubar {
     height: f64,
+    monitor_id: u32,
+    frame: Rect,
+    auxiliary_top_left_area: Option<Rect>,
+    auxiliary_top_right_area: Option<Rect>,
 }
 
 impl Menubar {
+    /// The height of the menubar when it is shown, even while it is auto-hidden, or
+    /// `0.0` if the monitor has none.
     pub fn height(&self) -> f64 {
-        #[cfg(target_os = "windows")]
-        {
-            unimplemented!()
-        }
+        self.height
+    }
+
+    /// The monitor the menubar is shown on.
+    pub fn monitor_id(&self) -> u32 {
+        self.monitor_id
+    }
+
+    pub fn frame(&self) -> Rect {
+        self.frame
+    }
+
+    /// The part of the menubar left of the camera housing, on screens with a notch.
+    pub fn auxiliary_top_left_area(&self) -> Option<Rect> {
+        self.auxiliary_top_left_area
+    }
+
+    /// The part of the menubar right of the camera housing, on screens with a notch.
+    pub fn auxiliary_top_right_area(&self) -> Option<Rect> {
+        self.auxiliary_top_right_area
+    }
+
+    /// The camera housing between the auxiliary areas, on screens with a notch.
+    pub fn notch(&self) -> Option<Rect> {
+        let left = self.auxiliary_top_left_area?;
 
-        #[cfg(target_os = "linux")]
-        {
-            unimplemented!()
-        }
+        let right = self.auxiliary_top_right_area?;
 
-        #[cfg(target_os = "macos")]
-        macos::menubar::get_height()
+        Some(Rect::new(
+            left.right(),
+            self.frame.y,
+            right.x - left.right(),
+            self.height,
+        ))
     }
 }
 
-/// Get info about the system-wide Menubar
-pub fn get_menubar() -> Menubar {
+/// Get the Menubar of the monitor `monitor_id`, or `None` if no such monitor is connected.
+pub fn get_menubar(monitor_id: u32) -> Option<Menubar> {
     #[cfg(target_os = "windows")]
     {
         unimplemented!()
     }
 
     #[cfg(target_os = "linux")]
+    {
+        linux::menubar::get_menubar(monitor_id)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::menubar::get_menubar(monitor_id)
+    }
+}
+
+/// Get the Menubar of every monitor. Monitors without one have a height of `0.0`.
+pub fn get_menubars() -> Vec<Menubar> {
+    #[cfg(target_os = "windows")]
     {
         unimplemented!()
     }
 
+    #[cfg(target_os = "linux")]
+    {
+        linux::menubar::get_menubars()
+    }
+
     #[cfg(target_os = "macos")]
     {
-        Menubar::default()
+        monitor::get_monitors()
+            .iter()
+            .filter_map(|monitor| macos::menubar::get_menubar(monitor.id()))
+            .collect()
     }
 }
@@ -0,0 +1,74 @@
+use monitor::{get_monitors, Monitor, Rect};
+
+use crate::Menubar;
+
+/// The height of GNOME Shell's top bar in logical pixels.
+const GNOME_TOP_BAR_HEIGHT: f64 = 32.0;
+
+pub fn get_menubar(monitor_id: u32) -> Option<Menubar> {
+    get_menubars()
+        .into_iter()
+        .find(|menubar| menubar.monitor_id == monitor_id)
+}
+
+/// Reads the top panel of each monitor from the area the monitor crate reserves for
+/// panels and docks: Mutter's per-monitor work areas, the `_NET_WM_STRUT_PARTIAL` of
+/// dock windows, or `_NET_WORKAREA`.
+pub fn get_menubars() -> Vec<Menubar> {
+    let monitors = get_monitors();
+
+    let heights = monitors
+        .iter()
+        .map(|monitor| monitor.visible_area().position().y - monitor.position().y)
+        .collect::<Vec<f64>>();
+
+    let is_wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+
+    // Wayland compositors may not tell XWayland clients about their panels at all.
+    let heights = if is_wayland && heights.iter().all(|height| *height <= 0.0) {
+        let desktop = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+
+        monitors
+            .iter()
+            .map(|monitor| guess_height(&desktop, monitor))
+            .collect()
+    } else {
+        heights
+    };
+
+    monitors
+        .iter()
+        .zip(heights)
+        .map(|(monitor, height)| Menubar {
+            height,
+            monitor_id: monitor.id(),
+            frame: Rect::new(
+                monitor.position().x,
+                monitor.position().y,
+                monitor.size().width,
+                height,
+            ),
+            auxiliary_top_left_area: None,
+            auxiliary_top_right_area: None,
+        })
+        .collect()
+}
+
+fn guess_height(desktop: &str, monitor: &Monitor) -> f64 {
+    guess_logical_height(desktop, monitor.is_primary()) * monitor.scale_factor()
+}
+
+/// A best guess at the top panel height, in logical pixels, for Wayland sessions.
+/// GNOME only shows its top bar on the primary monitor, and KDE Plasma and most
+/// other desktops put their panel at the bottom.
+pub fn guess_logical_height(desktop: &str, is_primary: bool) -> f64 {
+    let is_gnome = desktop
+        .split(':')
+        .any(|desktop| desktop.eq_ignore_ascii_case("GNOME"));
+
+    if is_gnome && is_primary {
+        GNOME_TOP_BAR_HEIGHT
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,6 @@
+pub mod menubar;
+pub mod status_notifier;
+pub mod system_bar;
+pub mod visibility;
+
+mod tests;
@@ -0,0 +1,147 @@
+use std::sync::{mpsc::Sender, Mutex};
+
+use tauri::PhysicalPosition;
+use zbus::{interface, object_server::SignalContext, zvariant::OwnedObjectPath};
+
+use super::{menu::MENU_PATH, Pixmap, ScrollOrientation, StatusNotifierConfig, ToolTip, TrayEvent};
+
+pub const ITEM_PATH: &str = "/StatusNotifierItem";
+
+/// The `org.kde.StatusNotifierItem` object.
+pub struct Item {
+    pub config: StatusNotifierConfig,
+    sender: Mutex<Sender<TrayEvent>>,
+}
+
+impl Item {
+    pub fn new(config: StatusNotifierConfig, sender: Sender<TrayEvent>) -> Self {
+        Self {
+            config,
+            sender: Mutex::new(sender),
+        }
+    }
+
+    fn send(&self, event: TrayEvent) {
+        let _ = self.sender.lock().unwrap().send(event);
+    }
+}
+
+#[interface(name = "org.kde.StatusNotifierItem")]
+impl Item {
+    fn activate(&self, x: i32, y: i32) {
+        crate::set_activation_position(PhysicalPosition::new(x as f64, y as f64));
+
+        self.send(TrayEvent::Activate { x, y });
+    }
+
+    fn secondary_activate(&self, x: i32, y: i32) {
+        crate::set_activation_position(PhysicalPosition::new(x as f64, y as f64));
+
+        self.send(TrayEvent::SecondaryActivate { x, y });
+    }
+
+    fn context_menu(&self, x: i32, y: i32) {
+        self.send(TrayEvent::ContextMenu { x, y });
+    }
+
+    fn scroll(&self, delta: i32, orientation: &str) {
+        let orientation = if orientation.eq_ignore_ascii_case("horizontal") {
+            ScrollOrientation::Horizontal
+        } else {
+            ScrollOrientation::Vertical
+        };
+
+        self.send(TrayEvent::Scroll { delta, orientation });
+    }
+
+    #[zbus(property)]
+    fn category(&self) -> String {
+        self.config.category.clone()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> String {
+        self.config.id.clone()
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> String {
+        self.config.title.clone()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        self.config.status.as_str().into()
+    }
+
+    #[zbus(property)]
+    fn window_id(&self) -> i32 {
+        0
+    }
+
+    #[zbus(property)]
+    fn icon_name(&self) -> String {
+        self.config.icon_name.clone()
+    }
+
+    #[zbus(property)]
+    fn icon_pixmap(&self) -> Vec<Pixmap> {
+        self.config.icon.clone()
+    }
+
+    #[zbus(property)]
+    fn overlay_icon_name(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn overlay_icon_pixmap(&self) -> Vec<Pixmap> {
+        vec![]
+    }
+
+    #[zbus(property)]
+    fn attention_icon_name(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn attention_icon_pixmap(&self) -> Vec<Pixmap> {
+        self.config.attention_icon.clone()
+    }
+
+    #[zbus(property)]
+    fn attention_movie_name(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn tool_tip(&self) -> ToolTip {
+        self.config.tool_tip.clone()
+    }
+
+    /// Clicks activate the item, and only right clicks open the menu.
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(MENU_PATH).unwrap()
+    }
+
+    #[zbus(signal)]
+    pub async fn new_title(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    pub async fn new_icon(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    pub async fn new_attention_icon(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    pub async fn new_tool_tip(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    pub async fn new_status(ctxt: &SignalContext<'_>, status: &str) -> zbus::Result<()>;
+}
@@ -0,0 +1,280 @@
+use std::{
+    collections::HashMap,
+    sync::{mpsc::Sender, Mutex},
+};
+
+use serde::Serialize;
+use zbus::{
+    fdo, interface,
+    object_server::SignalContext,
+    zvariant::{OwnedValue, StructureBuilder, Type, Value},
+};
+
+use super::{MenuItem, MenuItemKind, TrayEvent};
+
+pub const MENU_PATH: &str = "/MenuBar";
+
+/// An entry and the layout of its submenu, whose entries are `Layout`s wrapped in variants.
+#[derive(Serialize, Type, Debug)]
+#[zvariant(signature = "(ia{sv}av)")]
+pub struct Layout(
+    pub i32,
+    pub HashMap<String, OwnedValue>,
+    pub Vec<OwnedValue>,
+);
+
+impl Layout {
+    fn into_value(self) -> OwnedValue {
+        let structure = StructureBuilder::new()
+            .add_field(self.0)
+            .add_field(self.1)
+            .add_field(self.2)
+            .build();
+
+        owned(structure)
+    }
+}
+
+/// Only values holding file descriptors cannot be owned, and menus have none.
+fn owned<'a>(value: impl Into<Value<'a>>) -> OwnedValue {
+    OwnedValue::try_from(value.into()).unwrap()
+}
+
+/// The number of ids `item` and its submenu take up.
+fn count(item: &MenuItem) -> i32 {
+    1 + item.children.iter().map(count).sum::<i32>()
+}
+
+/// Assigns ids to the entries of `items` depth first, starting at `first_id`. The root
+/// of the menu has the id `0`.
+pub fn entries(items: &[MenuItem], first_id: i32) -> Vec<(i32, &MenuItem)> {
+    let mut entries = vec![];
+
+    let mut id = first_id;
+
+    for item in items {
+        entries.push((id, item));
+
+        entries.extend(self::entries(&item.children, id + 1));
+
+        id += count(item);
+    }
+
+    entries
+}
+
+/// The dbusmenu properties of `item`, limited to `names` unless it is empty.
+pub fn properties(item: &MenuItem, names: &[String]) -> HashMap<String, OwnedValue> {
+    let mut properties = HashMap::new();
+
+    match item.kind {
+        MenuItemKind::Separator => {
+            properties.insert("type".to_string(), owned("separator"));
+        }
+        MenuItemKind::Standard => {
+            properties.insert("label".to_string(), owned(item.label.as_str()));
+        }
+        MenuItemKind::Checkbox { checked } => {
+            properties.insert("label".to_string(), owned(item.label.as_str()));
+
+            properties.insert("toggle-type".to_string(), owned("checkmark"));
+
+            properties.insert("toggle-state".to_string(), owned(i32::from(checked)));
+        }
+    }
+
+    properties.insert("enabled".to_string(), owned(item.enabled));
+
+    properties.insert("visible".to_string(), owned(true));
+
+    if !item.children.is_empty() {
+        properties.insert("children-display".to_string(), owned("submenu"));
+    }
+
+    if !names.is_empty() {
+        properties.retain(|name, _| names.contains(name));
+    }
+
+    properties
+}
+
+/// The layout of the entry `id`, with `depth` levels of its submenu, or all of them
+/// when `depth` is `-1`.
+pub fn layout(
+    id: i32,
+    item: Option<&MenuItem>,
+    children: &[MenuItem],
+    depth: i32,
+    names: &[String],
+) -> Layout {
+    let properties = match item {
+        Some(item) => properties(item, names),
+        None => HashMap::from([("children-display".to_string(), owned("submenu"))]),
+    };
+
+    let mut children_layout = vec![];
+
+    if depth != 0 {
+        let mut child_id = id + 1;
+
+        for child in children {
+            children_layout.push(
+                layout(child_id, Some(child), &child.children, depth - 1, names).into_value(),
+            );
+
+            child_id += count(child);
+        }
+    }
+
+    Layout(id, properties, children_layout)
+}
+
+/// The `com.canonical.dbusmenu` object, which exports the menu of the item.
+pub struct Menu {
+    items: Vec<MenuItem>,
+    revision: u32,
+    sender: Mutex<Sender<TrayEvent>>,
+}
+
+impl Menu {
+    pub fn new(items: Vec<MenuItem>, sender: Sender<TrayEvent>) -> Self {
+        Self {
+            items,
+            revision: 1,
+            sender: Mutex::new(sender),
+        }
+    }
+
+    /// Replaces the menu, and returns the new revision of its layout.
+    pub fn set_items(&mut self, items: Vec<MenuItem>) -> u32 {
+        self.items = items;
+
+        self.revision += 1;
+
+        self.revision
+    }
+
+    fn find(&self, id: i32) -> Option<&MenuItem> {
+        entries(&self.items, 1)
+            .into_iter()
+            .find(|(entry_id, _)| *entry_id == id)
+            .map(|(_, item)| item)
+    }
+}
+
+#[interface(name = "com.canonical.dbusmenu")]
+impl Menu {
+    #[zbus(out_args("revision", "layout"))]
+    fn get_layout(
+        &self,
+        parent_id: i32,
+        recursion_depth: i32,
+        property_names: Vec<String>,
+    ) -> fdo::Result<(u32, Layout)> {
+        let layout = if parent_id == 0 {
+            layout(0, None, &self.items, recursion_depth, &property_names)
+        } else {
+            let item = self
+                .find(parent_id)
+                .ok_or_else(|| fdo::Error::InvalidArgs(format!("no menu item {}", parent_id)))?;
+
+            layout(
+                parent_id,
+                Some(item),
+                &item.children,
+                recursion_depth,
+                &property_names,
+            )
+        };
+
+        Ok((self.revision, layout))
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        property_names: Vec<String>,
+    ) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+        entries(&self.items, 1)
+            .into_iter()
+            .filter(|(id, _)| ids.is_empty() || ids.contains(id))
+            .map(|(id, item)| (id, properties(item, &property_names)))
+            .collect()
+    }
+
+    fn get_property(&self, id: i32, name: String) -> fdo::Result<OwnedValue> {
+        self.find(id)
+            .and_then(|item| properties(item, std::slice::from_ref(&name)).remove(&name))
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("no property {} on {}", name, id)))
+    }
+
+    fn event(&self, id: i32, event_id: String, _data: OwnedValue, _timestamp: u32) {
+        if event_id != "clicked" {
+            return;
+        }
+
+        if let Some(item) = self.find(id) {
+            let _ = self
+                .sender
+                .lock()
+                .unwrap()
+                .send(TrayEvent::MenuItemClicked {
+                    id: item.id.clone(),
+                });
+        }
+    }
+
+    /// Returns the ids of the entries that do not exist.
+    fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        let mut errors = vec![];
+
+        for (id, event_id, data, timestamp) in events {
+            if self.find(id).is_none() {
+                errors.push(id);
+
+                continue;
+            }
+
+            self.event(id, event_id, data, timestamp);
+        }
+
+        errors
+    }
+
+    /// The menu never changes when it is about to be shown.
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    #[zbus(out_args("updates_needed", "id_errors"))]
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (vec![], vec![])
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> String {
+        "ltr".into()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        "normal".into()
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        vec![]
+    }
+
+    #[zbus(signal)]
+    pub async fn layout_updated(
+        ctxt: &SignalContext<'_>,
+        revision: u32,
+        parent: i32,
+    ) -> zbus::Result<()>;
+}
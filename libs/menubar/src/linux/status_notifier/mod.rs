@@ -0,0 +1,380 @@
+use std::sync::mpsc::{self, Receiver};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zbus::{
+    blocking::Connection,
+    zvariant::{OwnedValue, Type, Value},
+};
+
+use self::{
+    item::{Item, ITEM_PATH},
+    menu::{Menu, MENU_PATH},
+};
+
+mod item;
+mod menu;
+mod tests;
+
+const WATCHER_SERVICE: &str = "org.kde.StatusNotifierWatcher";
+
+const WATCHER_PATH: &str = "/StatusNotifierWatcher";
+
+#[derive(Error, Debug)]
+pub enum StatusNotifierError {
+    #[error(transparent)]
+    Dbus(#[from] zbus::Error),
+    #[error(transparent)]
+    Fdo(#[from] zbus::fdo::Error),
+    #[error("no StatusNotifierWatcher is running; the desktop may not show tray icons")]
+    NoWatcher,
+}
+
+/// An icon in ARGB32 format, with the bytes of each pixel in network byte order.
+#[derive(Serialize, Deserialize, Type, Value, OwnedValue, Debug, Clone, PartialEq, Eq)]
+pub struct Pixmap {
+    pub width: i32,
+    pub height: i32,
+    pub data: Vec<u8>,
+}
+
+impl Pixmap {
+    /// Converts `width` by `height` RGBA pixels, e.g. from `image::RgbaImage::as_raw`.
+    pub fn from_rgba(width: i32, height: i32, rgba: &[u8]) -> Self {
+        let data = rgba
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[3], pixel[0], pixel[1], pixel[2]])
+            .collect();
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Value, OwnedValue, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolTip {
+    pub icon_name: String,
+    pub icon: Vec<Pixmap>,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Status {
+    /// The item is not important, and hosts may hide it.
+    Passive,
+    #[default]
+    Active,
+    /// The item asks for the user's attention, and hosts show its attention icon.
+    NeedsAttention,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Passive => "Passive",
+            Status::Active => "Active",
+            Status::NeedsAttention => "NeedsAttention",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuItemKind {
+    Standard,
+    Separator,
+    Checkbox { checked: bool },
+}
+
+/// An entry of the item's context menu, exported with `com.canonical.dbusmenu`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    /// Reported in `TrayEvent::MenuItemClicked` when the entry is clicked.
+    pub id: String,
+    pub label: String,
+    pub enabled: bool,
+    pub kind: MenuItemKind,
+    /// Entries of the submenu the entry opens.
+    pub children: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+            kind: MenuItemKind::Standard,
+            children: vec![],
+        }
+    }
+
+    pub fn separator() -> Self {
+        Self {
+            kind: MenuItemKind::Separator,
+            ..Self::new("", "")
+        }
+    }
+
+    pub fn checkbox(id: impl Into<String>, label: impl Into<String>, checked: bool) -> Self {
+        Self {
+            kind: MenuItemKind::Checkbox { checked },
+            ..Self::new(id, label)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollOrientation {
+    Horizontal,
+    Vertical,
+}
+
+/// What the host asked the item to do. Positions are where the host wants a window to
+/// appear, usually the pointer or the icon, in the coordinates monitors are reported in.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The icon was clicked.
+    Activate {
+        x: i32,
+        y: i32,
+    },
+    /// The icon was middle-clicked.
+    SecondaryActivate {
+        x: i32,
+        y: i32,
+    },
+    /// The icon was right-clicked by a host that does not show the exported menu.
+    ContextMenu {
+        x: i32,
+        y: i32,
+    },
+    Scroll {
+        delta: i32,
+        orientation: ScrollOrientation,
+    },
+    MenuItemClicked {
+        id: String,
+    },
+}
+
+/// The appearance of a `StatusNotifierItem`.
+#[derive(Debug, Clone)]
+pub struct StatusNotifierConfig {
+    /// A name for the item that stays the same between runs, e.g. the app's name.
+    pub id: String,
+    pub title: String,
+    /// `ApplicationStatus`, `Communications`, `SystemServices` or `Hardware`.
+    pub category: String,
+    pub status: Status,
+    /// A freedesktop icon name, preferred over `icon` by hosts that can find it.
+    pub icon_name: String,
+    /// The same icon in several sizes.
+    pub icon: Vec<Pixmap>,
+    pub attention_icon: Vec<Pixmap>,
+    pub tool_tip: ToolTip,
+    pub menu: Vec<MenuItem>,
+}
+
+impl Default for StatusNotifierConfig {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            title: String::new(),
+            category: "ApplicationStatus".into(),
+            status: Status::Active,
+            icon_name: String::new(),
+            icon: vec![],
+            attention_icon: vec![],
+            tool_tip: ToolTip::default(),
+            menu: vec![],
+        }
+    }
+}
+
+/// A tray icon published with the StatusNotifierItem protocol, which KDE Plasma, GNOME
+/// with the AppIndicator extension, and most panels show. Clicks are received as
+/// `TrayEvent`s, and the icon is removed when it is dropped.
+pub struct StatusNotifierItem {
+    connection: Connection,
+    service: String,
+    receiver: Receiver<TrayEvent>,
+}
+
+impl StatusNotifierItem {
+    /// Publishes the item on the session bus.
+    pub fn new(config: StatusNotifierConfig) -> Result<Self, StatusNotifierError> {
+        Self::with_connection(Connection::session()?, config)
+    }
+
+    /// Publishes the item on `connection` and registers it with the StatusNotifierWatcher.
+    pub fn with_connection(
+        connection: Connection,
+        config: StatusNotifierConfig,
+    ) -> Result<Self, StatusNotifierError> {
+        let (sender, receiver) = mpsc::channel();
+
+        let menu = Menu::new(config.menu.clone(), sender.clone());
+
+        connection
+            .object_server()
+            .at(ITEM_PATH, Item::new(config, sender))?;
+
+        connection.object_server().at(MENU_PATH, menu)?;
+
+        // Items are named after the process and an instance number, as the spec suggests.
+        let service = format!(
+            "org.kde.StatusNotifierItem-{}-{}",
+            std::process::id(),
+            next_instance()
+        );
+
+        connection.request_name(service.as_str())?;
+
+        let item = Self {
+            connection,
+            service,
+            receiver,
+        };
+
+        item.register()?;
+
+        Ok(item)
+    }
+
+    /// The bus name the item is published under.
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Registers the item with the StatusNotifierWatcher again, e.g. after the panel
+    /// restarted.
+    pub fn register(&self) -> Result<(), StatusNotifierError> {
+        let result = self.connection.call_method(
+            Some(WATCHER_SERVICE),
+            WATCHER_PATH,
+            Some(WATCHER_SERVICE),
+            "RegisterStatusNotifierItem",
+            &(self.service.as_str()),
+        );
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(zbus::Error::MethodError(name, _, _))
+                if name.as_str() == "org.freedesktop.DBus.Error.ServiceUnknown" =>
+            {
+                Err(StatusNotifierError::NoWatcher)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn item(
+        &self,
+    ) -> Result<zbus::blocking::object_server::InterfaceRef<Item>, StatusNotifierError> {
+        Ok(self
+            .connection
+            .object_server()
+            .interface::<_, Item>(ITEM_PATH)?)
+    }
+
+    pub fn set_title(&self, title: impl Into<String>) -> Result<(), StatusNotifierError> {
+        let item = self.item()?;
+
+        item.get_mut().config.title = title.into();
+
+        zbus::block_on(Item::new_title(item.signal_context()))?;
+
+        Ok(())
+    }
+
+    pub fn set_status(&self, status: Status) -> Result<(), StatusNotifierError> {
+        let item = self.item()?;
+
+        item.get_mut().config.status = status;
+
+        zbus::block_on(Item::new_status(item.signal_context(), status.as_str()))?;
+
+        Ok(())
+    }
+
+    pub fn set_icon(&self, icon: Vec<Pixmap>) -> Result<(), StatusNotifierError> {
+        let item = self.item()?;
+
+        item.get_mut().config.icon = icon;
+
+        zbus::block_on(Item::new_icon(item.signal_context()))?;
+
+        Ok(())
+    }
+
+    pub fn set_icon_name(&self, icon_name: impl Into<String>) -> Result<(), StatusNotifierError> {
+        let item = self.item()?;
+
+        item.get_mut().config.icon_name = icon_name.into();
+
+        zbus::block_on(Item::new_icon(item.signal_context()))?;
+
+        Ok(())
+    }
+
+    pub fn set_attention_icon(&self, icon: Vec<Pixmap>) -> Result<(), StatusNotifierError> {
+        let item = self.item()?;
+
+        item.get_mut().config.attention_icon = icon;
+
+        zbus::block_on(Item::new_attention_icon(item.signal_context()))?;
+
+        Ok(())
+    }
+
+    pub fn set_tool_tip(&self, tool_tip: ToolTip) -> Result<(), StatusNotifierError> {
+        let item = self.item()?;
+
+        item.get_mut().config.tool_tip = tool_tip;
+
+        zbus::block_on(Item::new_tool_tip(item.signal_context()))?;
+
+        Ok(())
+    }
+
+    pub fn set_menu(&self, items: Vec<MenuItem>) -> Result<(), StatusNotifierError> {
+        let menu = self
+            .connection
+            .object_server()
+            .interface::<_, Menu>(MENU_PATH)?;
+
+        let revision = menu.get_mut().set_items(items);
+
+        zbus::block_on(Menu::layout_updated(menu.signal_context(), revision, 0))?;
+
+        Ok(())
+    }
+
+    /// The `TrayEvent`s the host sends, in order. Iterating blocks until the next one.
+    pub fn events(&self) -> &Receiver<TrayEvent> {
+        &self.receiver
+    }
+}
+
+impl Drop for StatusNotifierItem {
+    fn drop(&mut self) {
+        // The watcher forgets items whose name goes away.
+        let _ = self.connection.release_name(self.service.as_str());
+
+        let _ = self.connection.object_server().remove::<Item, _>(ITEM_PATH);
+
+        let _ = self.connection.object_server().remove::<Menu, _>(MENU_PATH);
+    }
+}
+
+fn next_instance() -> usize {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static INSTANCE: AtomicUsize = AtomicUsize::new(1);
+
+    INSTANCE.fetch_add(1, Ordering::Relaxed)
+}
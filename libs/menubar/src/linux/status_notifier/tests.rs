@@ -0,0 +1,350 @@
+#![cfg(test)]
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use zbus::{
+    blocking::{connection, Connection, Proxy},
+    interface,
+    zvariant::{OwnedValue, Structure, Value},
+};
+
+use super::{
+    item::ITEM_PATH,
+    menu::{entries, MENU_PATH},
+    MenuItem, Pixmap, ScrollOrientation, Status, StatusNotifierConfig, StatusNotifierError,
+    StatusNotifierItem, ToolTip, TrayEvent, WATCHER_PATH, WATCHER_SERVICE,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The revision and root entry `GetLayout` replies with.
+type LayoutReply = (u32, (i32, HashMap<String, OwnedValue>, Vec<OwnedValue>));
+
+/// A session bus of its own, so tests neither need nor disturb the desktop's.
+struct Bus {
+    daemon: Child,
+    address: String,
+}
+
+impl Bus {
+    /// Starts the bus, failing the test if `dbus-daemon` is not installed, so that a
+    /// missing daemon does not pass as a green run.
+    fn start() -> Self {
+        let mut daemon = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("these tests need `dbus-daemon` on the PATH");
+
+        let mut address = String::new();
+
+        BufReader::new(daemon.stdout.take().unwrap())
+            .read_line(&mut address)
+            .expect("dbus-daemon did not print its address");
+
+        Self {
+            daemon,
+            address: address.trim().to_string(),
+        }
+    }
+
+    fn connect(&self) -> Connection {
+        connection::Builder::address(self.address.as_str())
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+}
+
+impl Drop for Bus {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+
+        let _ = self.daemon.wait();
+    }
+}
+
+/// Records the items that register, like the watcher a panel runs.
+struct Watcher {
+    items: Arc<Mutex<Vec<String>>>,
+}
+
+#[interface(name = "org.kde.StatusNotifierWatcher")]
+impl Watcher {
+    fn register_status_notifier_item(&self, service: String) {
+        self.items.lock().unwrap().push(service);
+    }
+}
+
+fn start_watcher(bus: &Bus) -> (Connection, Arc<Mutex<Vec<String>>>) {
+    let items = Arc::new(Mutex::new(vec![]));
+
+    let connection = connection::Builder::address(bus.address.as_str())
+        .unwrap()
+        .name(WATCHER_SERVICE)
+        .unwrap()
+        .serve_at(
+            WATCHER_PATH,
+            Watcher {
+                items: items.clone(),
+            },
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+    (connection, items)
+}
+
+fn config() -> StatusNotifierConfig {
+    StatusNotifierConfig {
+        id: "menubar-test".into(),
+        title: "Menubar".into(),
+        icon: vec![Pixmap::from_rgba(1, 1, &[255, 0, 0, 128])],
+        tool_tip: ToolTip {
+            title: "Menubar".into(),
+            description: "Click to open".into(),
+            ..Default::default()
+        },
+        menu: vec![
+            MenuItem::new("open", "Open"),
+            MenuItem {
+                children: vec![MenuItem::checkbox("autostart", "Start at login", true)],
+                ..MenuItem::new("settings", "Settings")
+            },
+            MenuItem::separator(),
+            MenuItem::new("quit", "Quit"),
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn it_converts_rgba_to_argb() {
+    let pixmap = Pixmap::from_rgba(2, 1, &[1, 2, 3, 4, 5, 6, 7, 8]);
+
+    assert_eq!(pixmap.data, vec![4, 1, 2, 3, 8, 5, 6, 7]);
+}
+
+#[test]
+fn it_numbers_menu_items_depth_first() {
+    let config = config();
+
+    let ids = entries(&config.menu, 1)
+        .into_iter()
+        .map(|(id, item)| (id, item.id.as_str()))
+        .collect::<Vec<(i32, &str)>>();
+
+    assert_eq!(
+        ids,
+        vec![
+            (1, "open"),
+            (2, "settings"),
+            (3, "autostart"),
+            (4, ""),
+            (5, "quit")
+        ]
+    );
+}
+
+#[test]
+fn it_registers_with_the_watcher() {
+    let bus = Bus::start();
+
+    // Without a watcher, nothing shows the item.
+    assert!(matches!(
+        StatusNotifierItem::with_connection(bus.connect(), config()),
+        Err(StatusNotifierError::NoWatcher)
+    ));
+
+    let (_watcher, items) = start_watcher(&bus);
+
+    let item = StatusNotifierItem::with_connection(bus.connect(), config()).unwrap();
+
+    assert_eq!(*items.lock().unwrap(), vec![item.service().to_string()]);
+
+    assert!(item.service().starts_with(&format!(
+        "org.kde.StatusNotifierItem-{}-",
+        std::process::id()
+    )));
+}
+
+#[test]
+fn it_publishes_the_icon_tool_tip_and_status() {
+    let bus = Bus::start();
+
+    let (_watcher, _) = start_watcher(&bus);
+
+    let item = StatusNotifierItem::with_connection(bus.connect(), config()).unwrap();
+
+    let host = bus.connect();
+
+    let proxy = Proxy::new(
+        &host,
+        item.service().to_string(),
+        ITEM_PATH,
+        "org.kde.StatusNotifierItem",
+    )
+    .unwrap();
+
+    assert_eq!(proxy.get_property::<String>("Id").unwrap(), "menubar-test");
+
+    assert_eq!(proxy.get_property::<String>("Status").unwrap(), "Active");
+
+    assert_eq!(
+        proxy.get_property::<Vec<Pixmap>>("IconPixmap").unwrap(),
+        vec![Pixmap {
+            width: 1,
+            height: 1,
+            data: vec![128, 255, 0, 0],
+        }]
+    );
+
+    assert_eq!(
+        proxy
+            .get_property::<ToolTip>("ToolTip")
+            .unwrap()
+            .description,
+        "Click to open"
+    );
+
+    let (sender, receiver) = mpsc::channel();
+
+    let signals = proxy.receive_signal("NewStatus").unwrap();
+
+    thread::spawn(move || {
+        for signal in signals {
+            let _ = sender.send(signal.body().deserialize::<String>().unwrap());
+        }
+    });
+
+    item.set_status(Status::NeedsAttention).unwrap();
+
+    assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap(), "NeedsAttention");
+
+    // Properties are cached until their change is signaled, so ask again.
+    let proxy = Proxy::new(
+        &host,
+        item.service().to_string(),
+        ITEM_PATH,
+        "org.kde.StatusNotifierItem",
+    )
+    .unwrap();
+
+    assert_eq!(
+        proxy.get_property::<String>("Status").unwrap(),
+        "NeedsAttention"
+    );
+}
+
+#[test]
+fn it_delivers_activations_with_coordinates() {
+    let bus = Bus::start();
+
+    let (_watcher, _) = start_watcher(&bus);
+
+    let item = StatusNotifierItem::with_connection(bus.connect(), config()).unwrap();
+
+    let host = bus.connect();
+
+    let proxy = Proxy::new(
+        &host,
+        item.service().to_string(),
+        ITEM_PATH,
+        "org.kde.StatusNotifierItem",
+    )
+    .unwrap();
+
+    proxy.call_method("Activate", &(1820, 12)).unwrap();
+
+    assert_eq!(
+        item.events().recv_timeout(TIMEOUT).unwrap(),
+        TrayEvent::Activate { x: 1820, y: 12 }
+    );
+
+    proxy.call_method("SecondaryActivate", &(1820, 12)).unwrap();
+
+    assert_eq!(
+        item.events().recv_timeout(TIMEOUT).unwrap(),
+        TrayEvent::SecondaryActivate { x: 1820, y: 12 }
+    );
+
+    proxy.call_method("Scroll", &(-120, "vertical")).unwrap();
+
+    assert_eq!(
+        item.events().recv_timeout(TIMEOUT).unwrap(),
+        TrayEvent::Scroll {
+            delta: -120,
+            orientation: ScrollOrientation::Vertical,
+        }
+    );
+
+    assert!(item.events().try_recv().is_err());
+}
+
+#[test]
+fn it_exports_the_menu() {
+    let bus = Bus::start();
+
+    let (_watcher, _) = start_watcher(&bus);
+
+    let item = StatusNotifierItem::with_connection(bus.connect(), config()).unwrap();
+
+    let host = bus.connect();
+
+    let proxy = Proxy::new(
+        &host,
+        item.service().to_string(),
+        MENU_PATH,
+        "com.canonical.dbusmenu",
+    )
+    .unwrap();
+
+    let (revision, (id, _, children)): LayoutReply = proxy
+        .call("GetLayout", &(0, -1, Vec::<String>::new()))
+        .unwrap();
+
+    assert_eq!(revision, 1);
+    assert_eq!(id, 0);
+    assert_eq!(children.len(), 4);
+
+    let settings = Structure::try_from(Value::from(children[1].try_clone().unwrap())).unwrap();
+
+    let fields = settings.fields();
+
+    assert_eq!(fields[0], Value::from(2));
+
+    let submenu = Vec::<OwnedValue>::try_from(fields[2].try_clone().unwrap()).unwrap();
+
+    assert_eq!(submenu.len(), 1);
+
+    let label: OwnedValue = proxy.call("GetProperty", &(3, "label")).unwrap();
+
+    assert_eq!(String::try_from(label).unwrap(), "Start at login");
+
+    proxy
+        .call_method("Event", &(5, "clicked", Value::from(0), 0u32))
+        .unwrap();
+
+    assert_eq!(
+        item.events().recv_timeout(TIMEOUT).unwrap(),
+        TrayEvent::MenuItemClicked { id: "quit".into() }
+    );
+
+    item.set_menu(vec![MenuItem::new("quit", "Quit")]).unwrap();
+
+    let (revision, (_, _, children)): LayoutReply = proxy
+        .call("GetLayout", &(0, -1, Vec::<String>::new()))
+        .unwrap();
+
+    assert_eq!(revision, 2);
+    assert_eq!(children.len(), 1);
+}
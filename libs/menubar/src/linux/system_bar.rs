@@ -0,0 +1,37 @@
+use monitor::LayoutMonitor;
+
+use crate::{
+    system_bar::{insets, Edge, SystemBar, SystemBarKind},
+    Menubar,
+};
+
+use super::menubar::get_menubars;
+
+pub fn get_system_bars(monitors: &[LayoutMonitor]) -> Vec<SystemBar> {
+    panels(monitors, &get_menubars())
+}
+
+/// Turns the reserved edges of each monitor into panels. `menubars` fills in top panels
+/// the work area does not show, which are guessed on Wayland.
+pub fn panels(monitors: &[LayoutMonitor], menubars: &[Menubar]) -> Vec<SystemBar> {
+    monitors
+        .iter()
+        .flat_map(|monitor| {
+            let mut insets = insets(monitor);
+
+            let top_panel = menubars
+                .iter()
+                .find(|menubar| menubar.monitor_id == monitor.id)
+                .map_or(0.0, |menubar| menubar.height);
+
+            if top_panel > 0.0 && !insets.iter().any(|(edge, _)| *edge == Edge::Top) {
+                insets.insert(0, (Edge::Top, top_panel));
+            }
+
+            // Panels that hide themselves do not reserve space, so they cannot be seen here.
+            insets.into_iter().map(move |(edge, thickness)| {
+                SystemBar::new(SystemBarKind::Panel, edge, thickness, monitor, false)
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,261 @@
+#![cfg(test)]
+
+use x11rb::{
+    connection::Connection,
+    protocol::xproto::{AtomEnum, ConnectionExt, CreateWindowAux, PropMode, WindowClass},
+    wrapper::ConnectionExt as _,
+    COPY_DEPTH_FROM_PARENT,
+};
+
+use monitor::{LayoutMonitor, Rect};
+
+use crate::{Edge, Menubar, SystemBarKind};
+
+use super::{
+    menubar::{get_menubars, guess_logical_height},
+    system_bar::panels,
+    visibility::StrutWatcher,
+};
+
+fn intern(conn: &impl Connection, name: &str) -> u32 {
+    conn.intern_atom(false, name.as_bytes())
+        .unwrap()
+        .reply()
+        .unwrap()
+        .atom
+}
+
+// Needs an X server, e.g. `xvfb-run -s "-screen 0 1920x1080x24" cargo test`.
+#[test]
+fn it_gets_the_height_of_a_top_dock() {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        return;
+    }
+
+    let (conn, screen_num) = match x11rb::connect(None) {
+        Ok(connection) => connection,
+        Err(_) => return,
+    };
+
+    let root = conn.setup().roots[screen_num].root;
+
+    let dock = conn.generate_id().unwrap();
+
+    conn.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        dock,
+        root,
+        0,
+        0,
+        300,
+        28,
+        0,
+        WindowClass::INPUT_OUTPUT,
+        0,
+        &CreateWindowAux::new().override_redirect(1),
+    )
+    .unwrap();
+
+    conn.change_property32(
+        PropMode::REPLACE,
+        dock,
+        intern(&conn, "_NET_WM_WINDOW_TYPE"),
+        AtomEnum::ATOM,
+        &[intern(&conn, "_NET_WM_WINDOW_TYPE_DOCK")],
+    )
+    .unwrap();
+
+    conn.change_property32(
+        PropMode::REPLACE,
+        dock,
+        intern(&conn, "_NET_WM_STRUT_PARTIAL"),
+        AtomEnum::CARDINAL,
+        &[0, 0, 28, 0, 0, 0, 0, 0, 0, 299, 0, 0],
+    )
+    .unwrap();
+
+    conn.map_window(dock).unwrap();
+
+    conn.sync().unwrap();
+
+    let menubars = get_menubars();
+
+    conn.destroy_window(dock).unwrap();
+
+    conn.sync().unwrap();
+
+    let monitor = monitor::get_monitors()
+        .into_iter()
+        .find(|monitor| monitor.position().x == 0.0 && monitor.position().y == 0.0)
+        .unwrap();
+
+    let menubar = menubars
+        .iter()
+        .find(|menubar| menubar.monitor_id() == monitor.id())
+        .unwrap();
+
+    assert_eq!(menubar.height(), 28.0);
+
+    // Monitors the dock does not span have no top panel.
+    assert!(menubars
+        .iter()
+        .filter(|menubar| menubar.monitor_id() != monitor.id())
+        .all(|menubar| menubar.height() == 0.0));
+}
+
+#[test]
+fn it_guesses_the_gnome_top_bar_on_the_primary_monitor_only() {
+    assert_eq!(guess_logical_height("ubuntu:GNOME", true), 32.0);
+
+    assert_eq!(guess_logical_height("GNOME", false), 0.0);
+}
+
+#[test]
+fn it_guesses_no_top_bar_on_other_desktops() {
+    assert_eq!(guess_logical_height("KDE", true), 0.0);
+
+    assert_eq!(guess_logical_height("", true), 0.0);
+}
+
+fn layout_monitor(id: u32, frame: Rect, visible_frame: Rect) -> LayoutMonitor {
+    LayoutMonitor {
+        id,
+        name: None,
+        frame,
+        visible_frame,
+        is_primary: id == 1,
+    }
+}
+
+#[test]
+fn it_reports_panels_on_every_reserved_edge() {
+    // A GNOME top bar on the primary monitor and a dock at the bottom of the other.
+    let monitors = [
+        layout_monitor(
+            1,
+            Rect::new(0.0, 0.0, 1920.0, 1080.0),
+            Rect::new(0.0, 32.0, 1920.0, 1048.0),
+        ),
+        layout_monitor(
+            2,
+            Rect::new(1920.0, 0.0, 1920.0, 1080.0),
+            Rect::new(1920.0, 0.0, 1920.0, 1024.0),
+        ),
+    ];
+
+    let bars = panels(&monitors, &[]);
+
+    assert_eq!(bars.len(), 2);
+
+    assert_eq!(bars[0].kind(), SystemBarKind::Panel);
+    assert_eq!(bars[0].edge(), Edge::Top);
+    assert_eq!(bars[0].monitor_id(), 1);
+    assert_eq!(bars[0].rect(), Rect::new(0.0, 0.0, 1920.0, 32.0));
+
+    assert_eq!(bars[1].edge(), Edge::Bottom);
+    assert_eq!(bars[1].monitor_id(), 2);
+    assert_eq!(bars[1].rect(), Rect::new(1920.0, 1024.0, 1920.0, 56.0));
+}
+
+#[test]
+fn it_reports_guessed_top_panels() {
+    let frame = Rect::new(0.0, 0.0, 2880.0, 1800.0);
+
+    let menubars = [Menubar {
+        height: 64.0,
+        monitor_id: 1,
+        frame: Rect::new(0.0, 0.0, 2880.0, 64.0),
+        ..Default::default()
+    }];
+
+    let bars = panels(&[layout_monitor(1, frame, frame)], &menubars);
+
+    assert_eq!(bars.len(), 1);
+    assert_eq!(bars[0].edge(), Edge::Top);
+    assert_eq!(bars[0].thickness(), 64.0);
+}
+
+#[test]
+fn it_gets_the_menubar_of_a_monitor() {
+    let monitor = match monitor::get_monitors().into_iter().next() {
+        Some(monitor) => monitor,
+        None => return,
+    };
+
+    let menubar = crate::get_menubar(monitor.id()).unwrap();
+
+    assert_eq!(menubar.frame().x, monitor.position().x);
+    assert_eq!(menubar.frame().width, monitor.size().width);
+    assert_eq!(menubar.frame().height, menubar.height());
+    assert!(menubar.notch().is_none());
+
+    assert!(crate::get_menubar(u32::MAX).is_none());
+}
+
+// Needs an X server, e.g. `xvfb-run -s "-screen 0 1920x1080x24" cargo test`.
+#[test]
+fn it_watches_panels_for_strut_changes() {
+    let mut watcher = match StrutWatcher::new() {
+        Some(watcher) => watcher,
+        None => return,
+    };
+
+    let (conn, screen_num) = x11rb::connect(None).unwrap();
+
+    let root = conn.setup().roots[screen_num].root;
+
+    let interval = std::time::Duration::from_millis(50);
+
+    let wait = |watcher: &mut StrutWatcher| {
+        std::thread::sleep(interval);
+
+        watcher.changed()
+    };
+
+    // Let events from other clients settle.
+    wait(&mut watcher);
+
+    let dock = conn.generate_id().unwrap();
+
+    conn.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        dock,
+        root,
+        0,
+        0,
+        300,
+        28,
+        0,
+        WindowClass::INPUT_OUTPUT,
+        0,
+        &CreateWindowAux::new().override_redirect(1),
+    )
+    .unwrap();
+
+    conn.map_window(dock).unwrap();
+
+    conn.sync().unwrap();
+
+    assert!(wait(&mut watcher));
+
+    assert!(!wait(&mut watcher));
+
+    conn.change_property32(
+        PropMode::REPLACE,
+        dock,
+        intern(&conn, "_NET_WM_STRUT_PARTIAL"),
+        AtomEnum::CARDINAL,
+        &[0, 0, 28, 0, 0, 0, 0, 0, 0, 299, 0, 0],
+    )
+    .unwrap();
+
+    conn.sync().unwrap();
+
+    assert!(wait(&mut watcher));
+
+    conn.destroy_window(dock).unwrap();
+
+    conn.sync().unwrap();
+
+    assert!(wait(&mut watcher));
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use x11rb::{
+    connection::Connection,
+    protocol::{
+        xproto::{Atom, AtomEnum, ChangeWindowAttributesAux, ConnectionExt, EventMask, Window},
+        Event,
+    },
+    rust_connection::RustConnection,
+};
+
+/// Root window properties the space reserved for panels is read from.
+const ROOT_PROPERTIES: [&str; 3] = ["_NET_WORKAREA", "_NET_CLIENT_LIST", "_NET_CURRENT_DESKTOP"];
+
+/// Mutter publishes a `_GTK_WORKAREAS_D<n>` property for each desktop.
+const GTK_WORKAREAS_PREFIX: &str = "_GTK_WORKAREAS_D";
+
+/// Window properties panels reserve space with.
+const STRUT_PROPERTIES: [&str; 2] = ["_NET_WM_STRUT", "_NET_WM_STRUT_PARTIAL"];
+
+/// Watches X11 panels for changes to the space they reserve along the screen edges.
+pub struct StrutWatcher {
+    conn: RustConnection,
+    root: Window,
+    atom_names: HashMap<Atom, String>,
+}
+
+impl StrutWatcher {
+    /// Connects to the X server, or returns `None` on Wayland, where XWayland clients
+    /// are not told about panels.
+    pub fn new() -> Option<Self> {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            return None;
+        }
+
+        let (conn, screen_num) = x11rb::connect(None).ok()?;
+
+        let root = conn.setup().roots[screen_num].root;
+
+        // Mapping and unmapping a panel changes the space reserved, and panels without a
+        // window manager are direct children of the root window.
+        conn.change_window_attributes(
+            root,
+            &ChangeWindowAttributesAux::new()
+                .event_mask(EventMask::PROPERTY_CHANGE | EventMask::SUBSTRUCTURE_NOTIFY),
+        )
+        .ok()?;
+
+        let watcher = Self {
+            conn,
+            root,
+            atom_names: HashMap::new(),
+        };
+
+        watcher.select_panels();
+
+        watcher.conn.flush().ok()?;
+
+        Some(watcher)
+    }
+
+    /// Listens for property changes on every window that may be a panel: the clients
+    /// the window manager lists, and the direct children of the root window.
+    fn select_panels(&self) {
+        let mut windows = self
+            .conn
+            .get_property(
+                false,
+                self.root,
+                self.atom("_NET_CLIENT_LIST").unwrap_or_default(),
+                AtomEnum::WINDOW,
+                0,
+                u32::MAX,
+            )
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| {
+                reply
+                    .value32()
+                    .map(|values| values.collect::<Vec<Window>>())
+            })
+            .unwrap_or_default();
+
+        if let Some(tree) = self
+            .conn
+            .query_tree(self.root)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+        {
+            windows.extend(tree.children);
+        }
+
+        for window in windows {
+            self.select(window);
+        }
+    }
+
+    fn select(&self, window: Window) {
+        // Windows may be destroyed before the request arrives, which is harmless.
+        let _ = self.conn.change_window_attributes(
+            window,
+            &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+        );
+    }
+
+    fn atom(&self, name: &str) -> Option<Atom> {
+        self.conn
+            .intern_atom(true, name.as_bytes())
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| reply.atom)
+    }
+
+    fn atom_name(&mut self, atom: Atom) -> &str {
+        let conn = &self.conn;
+
+        self.atom_names.entry(atom).or_insert_with(|| {
+            conn.get_atom_name(atom)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .map(|reply| String::from_utf8_lossy(&reply.name).into_owned())
+                .unwrap_or_default()
+        })
+    }
+
+    fn is_strut_property(&mut self, window: Window, atom: Atom) -> bool {
+        let is_root = window == self.root;
+
+        let name = self.atom_name(atom);
+
+        if is_root {
+            ROOT_PROPERTIES.contains(&name) || name.starts_with(GTK_WORKAREAS_PREFIX)
+        } else {
+            STRUT_PROPERTIES.contains(&name)
+        }
+    }
+
+    /// Returns whether panels were mapped, unmapped or changed the space they reserve
+    /// since the last call, without blocking.
+    pub fn changed(&mut self) -> bool {
+        let mut changed = false;
+
+        loop {
+            let event = match self.conn.poll_for_event() {
+                Ok(Some(event)) => event,
+                Ok(None) => break,
+                // Without a connection there is nothing to watch, so read the panels again.
+                Err(_) => return true,
+            };
+
+            match event {
+                Event::PropertyNotify(event) => {
+                    if !self.is_strut_property(event.window, event.atom) {
+                        continue;
+                    }
+
+                    // New clients are announced through `_NET_CLIENT_LIST`.
+                    if event.window == self.root && self.atom_name(event.atom) == "_NET_CLIENT_LIST"
+                    {
+                        self.select_panels();
+                    }
+
+                    changed = true;
+                }
+                Event::MapNotify(event) => {
+                    self.select(event.window);
+
+                    changed = true;
+                }
+                Event::UnmapNotify(_) | Event::DestroyNotify(_) => changed = true,
+                _ => {}
+            }
+        }
+
+        let _ = self.conn.flush();
+
+        changed
+    }
+}
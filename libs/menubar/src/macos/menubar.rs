@@ -1,6 +1,22 @@
-use cocoa::{appkit::CGFloat, base::id};
+use cocoa::{
+    appkit::CGFloat,
+    base::{id, nil, BOOL, YES},
+    foundation::{NSRect, NSString},
+};
+use monitor::Rect;
 use objc::{class, msg_send, sel, sel_impl};
 
+use crate::Menubar;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct NSEdgeInsets {
+    top: CGFloat,
+    left: CGFloat,
+    bottom: CGFloat,
+    right: CGFloat,
+}
+
 pub fn get_height() -> CGFloat {
     let status_bar: id = unsafe { msg_send![class!(NSStatusBar), systemStatusBar] };
 
@@ -8,3 +24,117 @@ pub fn get_height() -> CGFloat {
 
     menubar_height
 }
+
+fn screen_id(screen: id) -> u32 {
+    unsafe {
+        let description: id = msg_send![screen, deviceDescription];
+
+        let key = NSString::alloc(nil)
+            .init_str("NSScreenNumber")
+            .autorelease();
+
+        let number: id = msg_send![description, objectForKey: key];
+
+        msg_send![number, unsignedIntValue]
+    }
+}
+
+fn responds_to(object: id, selector: objc::runtime::Sel) -> bool {
+    let responds: BOOL = unsafe { msg_send![object, respondsToSelector: selector] };
+
+    responds == YES
+}
+
+/// Converts a rect from AppKit's coordinates, which grow upwards from the bottom-left
+/// corner of the primary screen, to a top-left origin.
+fn flip(rect: NSRect, primary_height: f64) -> Rect {
+    Rect::new(
+        rect.origin.x,
+        primary_height - rect.origin.y - rect.size.height,
+        rect.size.width,
+        rect.size.height,
+    )
+}
+
+/// Screens without a notch report an empty auxiliary area.
+fn auxiliary_area(area: NSRect, primary_height: f64) -> Option<Rect> {
+    if area.size.width <= 0.0 || area.size.height <= 0.0 {
+        return None;
+    }
+
+    Some(flip(area, primary_height))
+}
+
+pub fn get_menubar(monitor_id: u32) -> Option<Menubar> {
+    objc::rc::autoreleasepool(|| {
+        let screens: id = unsafe { msg_send![class!(NSScreen), screens] };
+
+        let count: usize = unsafe { msg_send![screens, count] };
+
+        let screens = (0..count)
+            .map(|index| unsafe { msg_send![screens, objectAtIndex: index] })
+            .collect::<Vec<id>>();
+
+        // The first screen is the one with the menubar, whose bottom-left corner is the origin.
+        let primary_frame: NSRect = unsafe { msg_send![*screens.first()?, frame] };
+
+        let primary_height = primary_frame.size.height;
+
+        let screen = *screens
+            .iter()
+            .find(|screen| screen_id(**screen) == monitor_id)?;
+
+        let frame: NSRect = unsafe { msg_send![screen, frame] };
+
+        let visible_frame: NSRect = unsafe { msg_send![screen, visibleFrame] };
+
+        // The menubar covers the top of the screen down to the visible frame, unless it
+        // is hidden, in which case it is reported as tall as it is when shown. On screens
+        // with a notch it is as tall as the camera housing.
+        let covered = (frame.origin.y + frame.size.height)
+            - (visible_frame.origin.y + visible_frame.size.height);
+
+        let safe_area_top = if responds_to(screen, sel!(safeAreaInsets)) {
+            let insets: NSEdgeInsets = unsafe { msg_send![screen, safeAreaInsets] };
+
+            insets.top
+        } else {
+            0.0
+        };
+
+        let separate_spaces: BOOL =
+            unsafe { msg_send![class!(NSScreen), screensHaveSeparateSpaces] };
+
+        let height = if covered > 0.0 {
+            covered
+        } else if separate_spaces == YES || screen == screens[0] {
+            get_height().max(safe_area_top)
+        } else {
+            0.0
+        };
+
+        let (auxiliary_top_left_area, auxiliary_top_right_area) =
+            if responds_to(screen, sel!(auxiliaryTopLeftArea)) {
+                let left: NSRect = unsafe { msg_send![screen, auxiliaryTopLeftArea] };
+
+                let right: NSRect = unsafe { msg_send![screen, auxiliaryTopRightArea] };
+
+                (
+                    auxiliary_area(left, primary_height),
+                    auxiliary_area(right, primary_height),
+                )
+            } else {
+                (None, None)
+            };
+
+        let frame = flip(frame, primary_height);
+
+        Some(Menubar {
+            height,
+            monitor_id,
+            frame: Rect::new(frame.x, frame.y, frame.width, height),
+            auxiliary_top_left_area,
+            auxiliary_top_right_area,
+        })
+    })
+}
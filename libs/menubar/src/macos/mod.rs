@@ -1,2 +1,5 @@
 pub mod menubar;
+pub mod system_bar;
 mod tests;
+pub mod tray;
+pub mod visibility;
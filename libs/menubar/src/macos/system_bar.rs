@@ -0,0 +1,104 @@
+use cocoa::{
+    base::{id, nil, BOOL, YES},
+    foundation::NSString,
+};
+use monitor::LayoutMonitor;
+use objc::{class, msg_send, sel, sel_impl};
+
+use crate::system_bar::{insets, Edge, SystemBar, SystemBarKind};
+
+use super::menubar::get_menubar;
+
+fn ns_string(string: &str) -> id {
+    unsafe { NSString::alloc(nil).init_str(string).autorelease() }
+}
+
+/// Reads `key` from the preferences of the app `domain`, e.g. `com.apple.dock`.
+fn preference(domain: &str, key: &str) -> id {
+    unsafe {
+        let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+
+        let preferences: id = msg_send![defaults, persistentDomainForName: ns_string(domain)];
+
+        if preferences == nil {
+            return nil;
+        }
+
+        msg_send![preferences, objectForKey: ns_string(key)]
+    }
+}
+
+pub(crate) fn bool_preference(domain: &str, key: &str) -> bool {
+    let value = preference(domain, key);
+
+    if value == nil {
+        return false;
+    }
+
+    let value: BOOL = unsafe { msg_send![value, boolValue] };
+
+    value == YES
+}
+
+fn dock_edge() -> Edge {
+    let orientation = preference("com.apple.dock", "orientation");
+
+    if orientation == nil {
+        return Edge::Bottom;
+    }
+
+    let orientation = unsafe {
+        let bytes: *const std::os::raw::c_char = msg_send![orientation, UTF8String];
+
+        std::ffi::CStr::from_ptr(bytes)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    match orientation.as_str() {
+        "left" => Edge::Left,
+        "right" => Edge::Right,
+        _ => Edge::Bottom,
+    }
+}
+
+pub fn get_system_bars(monitors: &[LayoutMonitor]) -> Vec<SystemBar> {
+    let hides_menubar = bool_preference("NSGlobalDomain", "_HIHideMenuBar");
+
+    let dock_edge = dock_edge();
+
+    let hides_dock = bool_preference("com.apple.dock", "autohide");
+
+    let mut bars = vec![];
+
+    for monitor in monitors {
+        // Without "Displays have separate Spaces", only the primary screen has a menubar.
+        let menubar_height = get_menubar(monitor.id).map_or(0.0, |menubar| menubar.height());
+
+        if menubar_height > 0.0 {
+            bars.push(SystemBar::new(
+                SystemBarKind::Menubar,
+                Edge::Top,
+                menubar_height,
+                monitor,
+                hides_menubar,
+            ));
+        }
+
+        let dock = insets(monitor)
+            .into_iter()
+            .find(|(edge, _)| *edge == dock_edge);
+
+        if let Some((edge, thickness)) = dock {
+            bars.push(SystemBar::new(
+                SystemBarKind::Dock,
+                edge,
+                thickness,
+                monitor,
+                hides_dock,
+            ));
+        }
+    }
+
+    bars
+}
@@ -6,3 +6,46 @@ fn it_gets_the_menubar_height() {
 
     assert!(menubar_height >= 22.0);
 }
+
+#[test]
+fn it_gets_the_menubar_of_the_primary_screen() {
+    let bars = crate::get_system_bars();
+
+    let layout = monitor::get_layout();
+
+    let primary = layout
+        .monitors()
+        .iter()
+        .find(|monitor| monitor.is_primary)
+        .unwrap();
+
+    let menubar = bars
+        .iter()
+        .find(|bar| bar.kind() == crate::SystemBarKind::Menubar && bar.monitor_id() == primary.id)
+        .unwrap();
+
+    assert!(menubar.thickness() >= super::menubar::get_height());
+
+    assert_eq!(menubar.rect().y, primary.frame.y);
+}
+
+#[test]
+fn it_gets_the_menubar_of_a_monitor() {
+    let monitor = monitor::get_monitors()
+        .into_iter()
+        .find(|monitor| monitor.is_primary())
+        .unwrap();
+
+    let menubar = crate::get_menubar(monitor.id()).unwrap();
+
+    assert_eq!(menubar.monitor_id(), monitor.id());
+
+    assert!(menubar.height() >= super::menubar::get_height());
+
+    assert_eq!(menubar.frame().height, menubar.height());
+
+    // The notch, if any, sits between the auxiliary areas.
+    if let Some(notch) = menubar.notch() {
+        assert!(notch.width > 0.0);
+    }
+}
@@ -0,0 +1,70 @@
+use std::ffi::CStr;
+
+use cocoa::{
+    appkit::NSApp,
+    base::{id, nil},
+    foundation::NSRect,
+};
+use monitor::Rect;
+use objc::{class, msg_send, runtime::Class, sel, sel_impl};
+
+use crate::tray::{Screen, TrayGeometry};
+
+fn class_name(object: id) -> String {
+    unsafe {
+        let class: *const Class = msg_send![object, class];
+
+        CStr::from_ptr((*class).name().as_ptr() as *const _)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Finds the window AppKit draws the app's status item in. Its frame is the frame of
+/// the item in the menubar.
+pub fn get_status_item(screens: &[Screen]) -> Option<TrayGeometry> {
+    objc::rc::autoreleasepool(|| {
+        let app = unsafe { NSApp() };
+
+        if app == nil {
+            return None;
+        }
+
+        let windows: id = unsafe { msg_send![app, windows] };
+
+        let count: usize = unsafe { msg_send![windows, count] };
+
+        let window = (0..count)
+            .map(|index| -> id { unsafe { msg_send![windows, objectAtIndex: index] } })
+            .find(|window| class_name(*window) == "NSStatusBarWindow")?;
+
+        let frame: NSRect = unsafe { msg_send![window, frame] };
+
+        // The first screen is the one with the menubar, whose bottom-left corner is the origin.
+        let screens_array: id = unsafe { msg_send![class!(NSScreen), screens] };
+
+        let primary_screen: id = unsafe { msg_send![screens_array, firstObject] };
+
+        if primary_screen == nil {
+            return None;
+        }
+
+        let primary_frame: NSRect = unsafe { msg_send![primary_screen, frame] };
+
+        let rect = Rect::new(
+            frame.origin.x,
+            primary_frame.size.height - frame.origin.y - frame.size.height,
+            frame.size.width,
+            frame.size.height,
+        );
+
+        let (x, y) = rect.center();
+
+        let monitor_id = screens
+            .iter()
+            .find(|screen| screen.frame.contains(x, y))
+            .map(|screen| screen.id);
+
+        Some(TrayGeometry::new(rect, monitor_id, true))
+    })
+}
@@ -0,0 +1,119 @@
+use cocoa::{
+    appkit::CGFloat,
+    base::{id, nil},
+    foundation::NSString,
+};
+use core_foundation::base::TCFType;
+use core_graphics::window::{
+    copy_window_info, kCGNullWindowID, kCGWindowListExcludeDesktopElements,
+    kCGWindowListOptionOnScreenOnly,
+};
+use monitor::{LayoutMonitor, Rect};
+use objc::{msg_send, sel, sel_impl};
+
+use crate::visibility::MenubarVisibility;
+
+use super::{menubar::get_menubar, system_bar::bool_preference};
+
+/// The window level of the menubar, `kCGMainMenuWindowLevel`.
+const MAIN_MENU_WINDOW_LEVEL: i64 = 24;
+
+fn ns_string(string: &str) -> id {
+    unsafe { NSString::alloc(nil).init_str(string).autorelease() }
+}
+
+fn to_string(string: id) -> String {
+    if string == nil {
+        return String::new();
+    }
+
+    unsafe {
+        let bytes: *const std::os::raw::c_char = msg_send![string, UTF8String];
+
+        std::ffi::CStr::from_ptr(bytes)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+fn number(dictionary: id, key: &str) -> CGFloat {
+    unsafe {
+        let value: id = msg_send![dictionary, objectForKey: ns_string(key)];
+
+        if value == nil {
+            return 0.0;
+        }
+
+        msg_send![value, doubleValue]
+    }
+}
+
+/// The frames of the menubar windows the Window Server has on screen, in points from
+/// the top-left corner of the primary screen. A fullscreen space has no menubar window,
+/// and an auto-hidden menubar is moved above the top of its screen.
+fn menubar_windows() -> Vec<Rect> {
+    let windows = match copy_window_info(
+        kCGWindowListOptionOnScreenOnly | kCGWindowListExcludeDesktopElements,
+        kCGNullWindowID,
+    ) {
+        Some(windows) => windows,
+        None => return vec![],
+    };
+
+    // CFArray and CFDictionary are toll-free bridged to NSArray and NSDictionary.
+    let windows = windows.as_concrete_TypeRef() as id;
+
+    let count: usize = unsafe { msg_send![windows, count] };
+
+    (0..count)
+        .map(|index| -> id { unsafe { msg_send![windows, objectAtIndex: index] } })
+        .filter(|window| {
+            let owner: id =
+                unsafe { msg_send![*window, objectForKey: ns_string("kCGWindowOwnerName")] };
+
+            number(*window, "kCGWindowLayer") as i64 == MAIN_MENU_WINDOW_LEVEL
+                && to_string(owner) == "Window Server"
+        })
+        .map(|window| {
+            let bounds: id =
+                unsafe { msg_send![window, objectForKey: ns_string("kCGWindowBounds")] };
+
+            Rect::new(
+                number(bounds, "X"),
+                number(bounds, "Y"),
+                number(bounds, "Width"),
+                number(bounds, "Height"),
+            )
+        })
+        .collect()
+}
+
+/// Whether a menubar window sits along the top edge of `frame`.
+fn is_shown(frame: &Rect, windows: &[Rect]) -> bool {
+    windows.iter().any(|window| {
+        window.height > 0.0 && window.y >= frame.y && window.intersection(frame).is_some()
+    })
+}
+
+pub fn get_menubar_visibility(monitors: &[LayoutMonitor]) -> Vec<MenubarVisibility> {
+    objc::rc::autoreleasepool(|| {
+        let auto_hide = bool_preference("NSGlobalDomain", "_HIHideMenuBar");
+
+        let windows = menubar_windows();
+
+        monitors
+            .iter()
+            .map(|monitor| {
+                let height = get_menubar(monitor.id).map_or(0.0, |menubar| menubar.height());
+
+                let shown = height > 0.0 && is_shown(&monitor.frame, &windows);
+
+                // Only tell a fullscreen space apart from a menubar that is always shown,
+                // since an auto-hidden menubar is hidden either way.
+                let fullscreen = height > 0.0 && !shown && !auto_hide;
+
+                MenubarVisibility::new(monitor.id, shown, height, auto_hide, fullscreen)
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,130 @@
+use monitor::{LayoutMonitor, Rect};
+use serde::{Deserialize, Serialize};
+
+mod tests;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemBarKind {
+    /// The macOS menubar.
+    Menubar,
+    /// The macOS Dock.
+    Dock,
+    /// A Linux desktop panel, such as the GNOME top bar or a KDE Plasma panel.
+    Panel,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Edge {
+    /// The strip of `frame` that is `thickness` deep along this edge.
+    pub fn strip(&self, frame: &Rect, thickness: f64) -> Rect {
+        match self {
+            Self::Top => Rect::new(frame.x, frame.y, frame.width, thickness),
+            Self::Bottom => Rect::new(frame.x, frame.bottom() - thickness, frame.width, thickness),
+            Self::Left => Rect::new(frame.x, frame.y, thickness, frame.height),
+            Self::Right => Rect::new(frame.right() - thickness, frame.y, thickness, frame.height),
+        }
+    }
+}
+
+/// A bar the OS reserves along an edge of a monitor. Geometry is in the coordinate
+/// space windows are positioned in: a top-left origin, logical points on macOS and
+/// physical pixels elsewhere.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SystemBar {
+    kind: SystemBarKind,
+    edge: Edge,
+    thickness: f64,
+    monitor_id: u32,
+    auto_hide: bool,
+    rect: Rect,
+}
+
+impl SystemBar {
+    pub(crate) fn new(
+        kind: SystemBarKind,
+        edge: Edge,
+        thickness: f64,
+        monitor: &LayoutMonitor,
+        auto_hide: bool,
+    ) -> Self {
+        Self {
+            kind,
+            edge,
+            thickness,
+            monitor_id: monitor.id,
+            auto_hide,
+            rect: edge.strip(&monitor.frame, thickness),
+        }
+    }
+
+    pub fn kind(&self) -> SystemBarKind {
+        self.kind
+    }
+
+    pub fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    /// How deep the bar reaches into the monitor from its edge.
+    pub fn thickness(&self) -> f64 {
+        self.thickness
+    }
+
+    pub fn monitor_id(&self) -> u32 {
+        self.monitor_id
+    }
+
+    /// Whether the bar hides until the pointer reaches its edge. An auto-hidden bar
+    /// does not reserve its space, so windows may cover its `rect`.
+    pub fn auto_hide(&self) -> bool {
+        self.auto_hide
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+}
+
+/// How deep each edge of `monitor.frame` is covered, going by its visible frame.
+/// Edges that are not covered are left out.
+pub(crate) fn insets(monitor: &LayoutMonitor) -> Vec<(Edge, f64)> {
+    let frame = &monitor.frame;
+
+    let visible_frame = &monitor.visible_frame;
+
+    [
+        (Edge::Top, visible_frame.y - frame.y),
+        (Edge::Bottom, frame.bottom() - visible_frame.bottom()),
+        (Edge::Left, visible_frame.x - frame.x),
+        (Edge::Right, frame.right() - visible_frame.right()),
+    ]
+    .into_iter()
+    .filter(|(_, inset)| *inset > 0.0)
+    .collect()
+}
+
+/// Returns every bar the OS reserves on the connected monitors, such as the macOS
+/// menubar and Dock, or the panels of a Linux desktop. Windows is not supported.
+pub fn get_system_bars() -> Vec<SystemBar> {
+    #[cfg(target_os = "windows")]
+    {
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::system_bar::get_system_bars(monitor::get_layout().monitors())
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::system_bar::get_system_bars(monitor::get_layout().monitors())
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg(test)]
+
+use monitor::{LayoutMonitor, Rect};
+
+use super::{insets, Edge, SystemBar, SystemBarKind};
+
+fn monitor(frame: Rect, visible_frame: Rect) -> LayoutMonitor {
+    LayoutMonitor {
+        id: 1,
+        name: None,
+        frame,
+        visible_frame,
+        is_primary: true,
+    }
+}
+
+#[test]
+fn it_measures_the_covered_edges() {
+    // A 25pt menubar and a 70pt Dock on the left.
+    let monitor = monitor(
+        Rect::new(0.0, 0.0, 1440.0, 900.0),
+        Rect::new(70.0, 25.0, 1370.0, 875.0),
+    );
+
+    assert_eq!(
+        insets(&monitor),
+        vec![(Edge::Top, 25.0), (Edge::Left, 70.0)]
+    );
+}
+
+#[test]
+fn it_measures_nothing_on_uncovered_monitors() {
+    let frame = Rect::new(1440.0, -200.0, 1920.0, 1080.0);
+
+    assert!(insets(&monitor(frame, frame)).is_empty());
+}
+
+#[test]
+fn it_places_bars_along_their_edge() {
+    let monitor = monitor(
+        Rect::new(-1920.0, 100.0, 1920.0, 1080.0),
+        Rect::new(-1920.0, 100.0, 1920.0, 1032.0),
+    );
+
+    let bar = SystemBar::new(SystemBarKind::Panel, Edge::Bottom, 48.0, &monitor, false);
+
+    assert_eq!(bar.rect(), Rect::new(-1920.0, 1132.0, 1920.0, 48.0));
+
+    assert_eq!(
+        Edge::Right.strip(&monitor.frame, 60.0),
+        Rect::new(-60.0, 100.0, 60.0, 1080.0)
+    );
+}
@@ -0,0 +1,263 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use monitor::{get_cursor_position, get_layout, get_monitors, Rect};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    tray::{TrayIcon, TrayIconEvent},
+    PhysicalPosition, Runtime,
+};
+
+mod tests;
+
+/// The size of a tray icon, in logical units, assumed when only the pointer position is known.
+const ICON_SIZE: f64 = 24.0;
+
+/// How long an activation position is used for after the host sends it, long enough
+/// for the click that caused it to be handled.
+const ACTIVATION_LIFETIME: Duration = Duration::from_millis(500);
+
+/// The last position a StatusNotifierItem host asked us to activate at, and when.
+static ACTIVATION_POSITION: Mutex<Option<(PhysicalPosition<f64>, Instant)>> = Mutex::new(None);
+
+/// Where a tray icon or status item sits on screen, in the coordinate space windows are
+/// positioned in: a top-left origin, logical points on macOS and physical pixels elsewhere.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TrayGeometry {
+    rect: Rect,
+    monitor_id: Option<u32>,
+    exact: bool,
+}
+
+impl TrayGeometry {
+    pub(crate) fn new(rect: Rect, monitor_id: Option<u32>, exact: bool) -> Self {
+        Self {
+            rect,
+            monitor_id,
+            exact,
+        }
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// The monitor the icon sits on.
+    pub fn monitor_id(&self) -> Option<u32> {
+        self.monitor_id
+    }
+
+    /// Whether `rect` is the icon's frame, rather than an icon-sized square around the
+    /// position it was clicked at.
+    pub fn exact(&self) -> bool {
+        self.exact
+    }
+
+    /// Reads the icon's frame from a tray event.
+    pub fn from_event(event: &TrayIconEvent) -> Option<Self> {
+        let rect = match event {
+            TrayIconEvent::Click { rect, .. }
+            | TrayIconEvent::DoubleClick { rect, .. }
+            | TrayIconEvent::Enter { rect, .. }
+            | TrayIconEvent::Move { rect, .. }
+            | TrayIconEvent::Leave { rect, .. } => rect,
+            #[allow(unreachable_patterns)]
+            _ => return None,
+        };
+
+        from_tauri_rect(rect)
+    }
+}
+
+/// A monitor in the coordinate space windows are positioned in, with the scale factor
+/// that takes it to the physical pixels tray positions are reported in.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Screen {
+    pub id: u32,
+    pub frame: Rect,
+    pub scale_factor: f64,
+}
+
+pub(crate) fn screens() -> Vec<Screen> {
+    let monitors = get_monitors();
+
+    get_layout()
+        .monitors()
+        .iter()
+        .map(|layout_monitor| {
+            // Only macOS positions windows in logical points.
+            let scale_factor = if cfg!(target_os = "macos") {
+                monitors
+                    .iter()
+                    .find(|monitor| monitor.id() == layout_monitor.id)
+                    .map_or(1.0, |monitor| monitor.scale_factor())
+            } else {
+                1.0
+            };
+
+            Screen {
+                id: layout_monitor.id,
+                frame: layout_monitor.frame,
+                scale_factor,
+            }
+        })
+        .collect()
+}
+
+/// Converts `rect`, in the physical pixels of whichever monitor it is on, to the
+/// coordinate space of `screens`, and finds that monitor.
+pub(crate) fn locate(rect: &Rect, screens: &[Screen]) -> Option<(Rect, u32)> {
+    screens.iter().find_map(|screen| {
+        let scale_factor = screen.scale_factor;
+
+        let scaled = Rect::new(
+            rect.x / scale_factor,
+            rect.y / scale_factor,
+            rect.width / scale_factor,
+            rect.height / scale_factor,
+        );
+
+        let (x, y) = scaled.center();
+
+        screen.frame.contains(x, y).then_some((scaled, screen.id))
+    })
+}
+
+/// An icon-sized square centered on `position`, a physical position the icon was clicked at.
+pub(crate) fn around(position: PhysicalPosition<f64>, screens: &[Screen]) -> TrayGeometry {
+    let point = Rect::new(position.x, position.y, 0.0, 0.0);
+
+    let (center, monitor_id, scale_factor) = match locate(&point, screens) {
+        Some((center, id)) => {
+            let scale_factor = screens
+                .iter()
+                .find(|screen| screen.id == id)
+                .map_or(1.0, |screen| screen.scale_factor);
+
+            (center, Some(id), scale_factor)
+        }
+        None => (point, None, 1.0),
+    };
+
+    // Icons are about as big in physical pixels as in logical units times the scale.
+    let size = if cfg!(target_os = "macos") {
+        ICON_SIZE
+    } else {
+        ICON_SIZE * scale_factor
+    };
+
+    TrayGeometry::new(
+        Rect::new(center.x - size / 2.0, center.y - size / 2.0, size, size),
+        monitor_id,
+        false,
+    )
+}
+
+fn from_tauri_rect(rect: &tauri::Rect) -> Option<TrayGeometry> {
+    let screens = screens();
+
+    let (rect, monitor_id) = match (rect.position, rect.size) {
+        (tauri::Position::Physical(position), tauri::Size::Physical(size)) => {
+            let physical = Rect::new(
+                position.x as f64,
+                position.y as f64,
+                size.width as f64,
+                size.height as f64,
+            );
+
+            match locate(&physical, &screens) {
+                Some((rect, id)) => (rect, Some(id)),
+                None => (physical, None),
+            }
+        }
+        (tauri::Position::Logical(position), tauri::Size::Logical(size)) => {
+            let rect = Rect::new(position.x, position.y, size.width, size.height);
+
+            let (x, y) = rect.center();
+
+            let monitor_id = screens
+                .iter()
+                .find(|screen| screen.frame.contains(x, y))
+                .map(|screen| screen.id);
+
+            (rect, monitor_id)
+        }
+        _ => return None,
+    };
+
+    if rect.width <= 0.0 || rect.height <= 0.0 {
+        return None;
+    }
+
+    Some(TrayGeometry::new(rect, monitor_id, true))
+}
+
+/// The activation position, unless it is older than `ACTIVATION_LIFETIME` at `now`.
+fn recent_activation(
+    activation: Option<(PhysicalPosition<f64>, Instant)>,
+    now: Instant,
+) -> Option<PhysicalPosition<f64>> {
+    activation
+        .filter(|(_, activated_at)| now.duration_since(*activated_at) <= ACTIVATION_LIFETIME)
+        .map(|(position, _)| position)
+}
+
+/// Estimates where the icon is from the last activation position, or else the pointer.
+fn estimate() -> Option<TrayGeometry> {
+    // The position is kept rather than taken, so every caller handling the same click
+    // sees it. It expires soon after, so later calls fall back to the pointer.
+    let activation = *ACTIVATION_POSITION.lock().unwrap();
+
+    let position = recent_activation(activation, Instant::now()).or_else(get_cursor_position)?;
+
+    Some(around(position, &screens()))
+}
+
+/// Records where a StatusNotifierItem host asked to activate the app's item, which
+/// `get_status_item` reports on Linux, where hosts do not expose icon frames.
+pub fn set_activation_position(position: PhysicalPosition<f64>) {
+    *ACTIVATION_POSITION.lock().unwrap() = Some((position, Instant::now()));
+}
+
+/// Returns where the app's status item sits. On Linux, this is estimated from the last
+/// activation position if it is recent, or else the pointer, since hosts do not expose
+/// icon frames; call it while handling a click.
+pub fn get_status_item() -> Option<TrayGeometry> {
+    #[cfg(target_os = "windows")]
+    {
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        estimate()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::tray::get_status_item(&screens())
+    }
+}
+
+pub trait TrayIconExt {
+    /// Returns where the tray icon sits, falling back to the pointer position like
+    /// `get_status_item` where the platform does not report icon frames.
+    fn geometry(&self) -> Option<TrayGeometry>;
+}
+
+impl<R: Runtime> TrayIconExt for TrayIcon<R> {
+    fn geometry(&self) -> Option<TrayGeometry> {
+        if let Some(geometry) = self
+            .rect()
+            .ok()
+            .flatten()
+            .and_then(|rect| from_tauri_rect(&rect))
+        {
+            return Some(geometry);
+        }
+
+        estimate()
+    }
+}
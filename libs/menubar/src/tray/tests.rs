@@ -0,0 +1,100 @@
+#![cfg(test)]
+
+use std::time::{Duration, Instant};
+
+use monitor::Rect;
+use tauri::PhysicalPosition;
+
+use super::{around, locate, recent_activation, Screen, ACTIVATION_LIFETIME};
+
+/// A Retina laptop screen with a standard-density monitor to its right, positioned
+/// in points like macOS does.
+fn mixed_screens() -> Vec<Screen> {
+    vec![
+        Screen {
+            id: 1,
+            frame: Rect::new(0.0, 0.0, 1440.0, 900.0),
+            scale_factor: 2.0,
+        },
+        Screen {
+            id: 2,
+            frame: Rect::new(1440.0, 0.0, 1920.0, 1080.0),
+            scale_factor: 1.0,
+        },
+    ]
+}
+
+#[test]
+fn it_converts_icon_frames_with_the_scale_of_their_monitor() {
+    let screens = mixed_screens();
+
+    // An icon near the right of the Retina menubar.
+    let (rect, id) = locate(&Rect::new(2600.0, 0.0, 48.0, 48.0), &screens).unwrap();
+
+    assert_eq!(id, 1);
+    assert_eq!(rect, Rect::new(1300.0, 0.0, 24.0, 24.0));
+
+    // The same physical position is past the Retina screen at 1x.
+    let (rect, id) = locate(&Rect::new(3000.0, 0.0, 24.0, 24.0), &screens).unwrap();
+
+    assert_eq!(id, 2);
+    assert_eq!(rect, Rect::new(3000.0, 0.0, 24.0, 24.0));
+}
+
+#[test]
+fn it_does_not_locate_icons_off_screen() {
+    assert!(locate(&Rect::new(-500.0, -500.0, 24.0, 24.0), &mixed_screens()).is_none());
+}
+
+#[test]
+fn it_estimates_icons_around_the_click_position() {
+    // Linux positions windows in physical pixels, so screens are never scaled.
+    let screens = vec![
+        Screen {
+            id: 1,
+            frame: Rect::new(0.0, 0.0, 1920.0, 1080.0),
+            scale_factor: 1.0,
+        },
+        Screen {
+            id: 2,
+            frame: Rect::new(1920.0, 0.0, 3840.0, 2160.0),
+            scale_factor: 1.0,
+        },
+    ];
+
+    let geometry = around(PhysicalPosition::new(4000.0, 20.0), &screens);
+
+    assert_eq!(geometry.monitor_id(), Some(2));
+    assert!(!geometry.exact());
+
+    let (x, y) = geometry.rect().center();
+
+    assert_eq!((x, y), (4000.0, 20.0));
+    assert!(geometry.rect().width > 0.0);
+
+    let geometry = around(PhysicalPosition::new(-10.0, -10.0), &screens);
+
+    assert_eq!(geometry.monitor_id(), None);
+}
+
+#[test]
+fn it_forgets_activation_positions_after_a_while() {
+    let position = PhysicalPosition::new(4000.0, 20.0);
+
+    let activated_at = Instant::now();
+
+    assert_eq!(
+        recent_activation(Some((position, activated_at)), activated_at),
+        Some(position)
+    );
+
+    assert_eq!(
+        recent_activation(
+            Some((position, activated_at)),
+            activated_at + ACTIVATION_LIFETIME + Duration::from_millis(1)
+        ),
+        None
+    );
+
+    assert_eq!(recent_activation(None, activated_at), None);
+}
@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use monitor::PollingStream;
+use serde::{Deserialize, Serialize};
+
+mod tests;
+
+/// Whether the menubar of a monitor is shown.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MenubarVisibility {
+    monitor_id: u32,
+    visible: bool,
+    height: f64,
+    auto_hide: bool,
+    fullscreen: bool,
+}
+
+impl MenubarVisibility {
+    pub(crate) fn new(
+        monitor_id: u32,
+        visible: bool,
+        height: f64,
+        auto_hide: bool,
+        fullscreen: bool,
+    ) -> Self {
+        Self {
+            monitor_id,
+            visible,
+            height,
+            auto_hide,
+            fullscreen,
+        }
+    }
+
+    pub fn monitor_id(&self) -> u32 {
+        self.monitor_id
+    }
+
+    /// Whether the menubar is on screen, and panels anchored to it have something to sit on.
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// The height of the menubar when it is shown.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// Whether the menubar hides until the pointer reaches the top of the screen.
+    pub fn auto_hide(&self) -> bool {
+        self.auto_hide
+    }
+
+    /// Whether the monitor shows a fullscreen space, which hides the menubar.
+    pub fn fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+}
+
+/// What made the menubar appear or disappear.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VisibilityChange {
+    /// Auto-hiding was toggled, or the pointer revealed or left an auto-hidden menubar.
+    AutoHide,
+    /// A fullscreen space was entered or left.
+    Fullscreen,
+    /// A panel reserving the top of the monitor appeared, disappeared or was resized.
+    Panel,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VisibilityEvent {
+    visibility: MenubarVisibility,
+    change: VisibilityChange,
+}
+
+impl VisibilityEvent {
+    /// The visibility of the menubar after the change.
+    pub fn visibility(&self) -> &MenubarVisibility {
+        &self.visibility
+    }
+
+    pub fn change(&self) -> VisibilityChange {
+        self.change
+    }
+}
+
+/// Compares the visibility of each monitor's menubar before and after, and reports
+/// what changed. Monitors that were connected or disconnected in between are skipped.
+pub(crate) fn changes(
+    previous: &[MenubarVisibility],
+    current: &[MenubarVisibility],
+) -> Vec<VisibilityEvent> {
+    current
+        .iter()
+        .filter_map(|visibility| {
+            let before = previous
+                .iter()
+                .find(|before| before.monitor_id == visibility.monitor_id)?;
+
+            let change = if before.fullscreen != visibility.fullscreen {
+                VisibilityChange::Fullscreen
+            } else if before.auto_hide != visibility.auto_hide {
+                VisibilityChange::AutoHide
+            } else if before.visible != visibility.visible || before.height != visibility.height {
+                if visibility.auto_hide {
+                    VisibilityChange::AutoHide
+                } else {
+                    VisibilityChange::Panel
+                }
+            } else {
+                return None;
+            };
+
+            Some(VisibilityEvent {
+                visibility: visibility.clone(),
+                change,
+            })
+        })
+        .collect()
+}
+
+/// Get whether the menubar of every monitor is shown.
+pub fn get_menubar_visibility() -> Vec<MenubarVisibility> {
+    #[cfg(target_os = "windows")]
+    {
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::get_menubars()
+            .iter()
+            .map(|menubar| {
+                MenubarVisibility::new(
+                    menubar.monitor_id(),
+                    menubar.height() > 0.0,
+                    menubar.height(),
+                    false,
+                    false,
+                )
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::visibility::get_menubar_visibility(monitor::get_layout().monitors())
+    }
+}
+
+/// Whether the menubar of the monitor `monitor_id` is shown.
+pub fn is_menubar_visible(monitor_id: u32) -> bool {
+    get_menubar_visibility()
+        .iter()
+        .any(|visibility| visibility.monitor_id == monitor_id && visibility.visible)
+}
+
+/// Receives a `VisibilityEvent` every time a menubar appears, disappears or is resized.
+/// Watching stops when the stream is dropped.
+pub type VisibilityStream = PollingStream<VisibilityEvent>;
+
+/// Watches the menubar of every monitor, and reports each time one appears, disappears
+/// or is resized. On macOS, the menubar is checked every `interval` for auto-hiding and
+/// fullscreen spaces. On X11, panels are watched for changes to the space they reserve,
+/// which are checked for every `interval`; elsewhere, the panels are read again instead.
+pub fn watch_menubar_visibility(interval: Duration) -> VisibilityStream {
+    PollingStream::spawn(interval, || {
+        // On X11, the menubar is only read again after a panel's struts change.
+        #[cfg(target_os = "linux")]
+        let mut watcher = crate::linux::visibility::StrutWatcher::new();
+
+        let mut last = get_menubar_visibility();
+
+        Some(move || {
+            #[cfg(target_os = "linux")]
+            let changed = watcher.as_mut().map_or(true, |watcher| watcher.changed());
+
+            #[cfg(not(target_os = "linux"))]
+            let changed = true;
+
+            if !changed {
+                return Vec::new();
+            }
+
+            let current = get_menubar_visibility();
+
+            let events = changes(&last, &current);
+
+            last = current;
+
+            events
+        })
+    })
+}
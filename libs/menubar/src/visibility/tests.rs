@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use super::{changes, MenubarVisibility, VisibilityChange};
+
+fn shown(monitor_id: u32) -> MenubarVisibility {
+    MenubarVisibility::new(monitor_id, true, 25.0, false, false)
+}
+
+#[test]
+fn it_reports_nothing_when_nothing_changes() {
+    let visibility = [shown(1), shown(2)];
+
+    assert!(changes(&visibility, &visibility).is_empty());
+}
+
+#[test]
+fn it_reports_fullscreen_spaces() {
+    let entered = MenubarVisibility::new(2, false, 25.0, false, true);
+
+    let events = changes(&[shown(1), shown(2)], &[shown(1), entered.clone()]);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].change(), VisibilityChange::Fullscreen);
+    assert_eq!(events[0].visibility(), &entered);
+
+    let events = changes(&[entered], &[shown(2)]);
+
+    assert_eq!(events[0].change(), VisibilityChange::Fullscreen);
+    assert!(events[0].visibility().visible());
+}
+
+fn auto_hidden(visible: bool) -> MenubarVisibility {
+    MenubarVisibility::new(1, visible, 25.0, true, false)
+}
+
+#[test]
+fn it_reports_auto_hiding() {
+    let events = changes(&[shown(1)], &[auto_hidden(false)]);
+
+    assert_eq!(events[0].change(), VisibilityChange::AutoHide);
+
+    // The pointer reveals an auto-hidden menubar.
+    let events = changes(&[auto_hidden(false)], &[auto_hidden(true)]);
+
+    assert_eq!(events[0].change(), VisibilityChange::AutoHide);
+}
+
+#[test]
+fn it_reports_panels() {
+    let resized = MenubarVisibility::new(1, true, 32.0, false, false);
+
+    let removed = MenubarVisibility::new(1, false, 0.0, false, false);
+
+    assert_eq!(
+        changes(&[shown(1)], &[resized])[0].change(),
+        VisibilityChange::Panel
+    );
+
+    assert_eq!(
+        changes(&[shown(1)], &[removed])[0].change(),
+        VisibilityChange::Panel
+    );
+
+    // Monitors that were just connected have nothing to compare with.
+    assert!(changes(&[shown(1)], &[shown(1), shown(2)]).is_empty());
+}
@@ -0,0 +1,159 @@
+use image::{imageops, RgbaImage};
+use thiserror::Error;
+
+use crate::{desktop, get_monitors, Rect};
+
+mod tests;
+
+#[derive(Error, Debug)]
+pub enum CaptureError {
+    #[error("no monitor with id {0} is connected")]
+    MonitorNotFound(u32),
+    #[error("the rect does not overlap any monitor")]
+    OutOfBounds,
+    #[error("the screenshot was cancelled")]
+    Cancelled,
+    #[error("failed to capture the screen: {0}")]
+    Failed(String),
+    #[error("failed to read the screenshot: {0}")]
+    Image(#[from] image::ImageError),
+}
+
+/// How the pixels of a raw framebuffer image are laid out.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PixelFormat {
+    pub bits_per_pixel: usize,
+    pub bytes_per_row: usize,
+    pub red_mask: u32,
+    pub green_mask: u32,
+    pub blue_mask: u32,
+    pub big_endian: bool,
+}
+
+/// Converts a framebuffer image with 16, 24 or 32 bits per pixel to opaque RGBA.
+/// Pixels missing from a truncated `data` are left transparent.
+pub(crate) fn to_rgba(data: &[u8], width: u32, height: u32, format: &PixelFormat) -> RgbaImage {
+    let bytes_per_pixel = format.bits_per_pixel / 8;
+
+    let channel = |pixel: u32, mask: u32| {
+        let bits = mask.count_ones();
+
+        let value = (pixel & mask) >> mask.trailing_zeros();
+
+        match bits {
+            0 => 0,
+            8 => value as u8,
+            bits => (value * 255 / ((1 << bits) - 1)) as u8,
+        }
+    };
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        let offset = y as usize * format.bytes_per_row + x as usize * bytes_per_pixel;
+
+        let bytes = match data.get(offset..offset + bytes_per_pixel) {
+            Some(bytes) if bytes_per_pixel > 0 => bytes,
+            _ => return image::Rgba([0, 0, 0, 0]),
+        };
+
+        let pixel = if format.big_endian {
+            bytes
+                .iter()
+                .fold(0u32, |pixel, byte| (pixel << 8) | *byte as u32)
+        } else {
+            bytes
+                .iter()
+                .rev()
+                .fold(0u32, |pixel, byte| (pixel << 8) | *byte as u32)
+        };
+
+        image::Rgba([
+            channel(pixel, format.red_mask),
+            channel(pixel, format.green_mask),
+            channel(pixel, format.blue_mask),
+            255,
+        ])
+    })
+}
+
+/// Resizes `image` to `width` x `height` if it is not that size already. Backends
+/// may return images at a different density than the monitor reports, e.g. the
+/// highest scale factor among the monitors a rect spans on macOS.
+pub(crate) fn fit(image: RgbaImage, width: u32, height: u32) -> RgbaImage {
+    if image.dimensions() == (width, height) || width == 0 || height == 0 {
+        return image;
+    }
+
+    imageops::resize(&image, width, height, imageops::FilterType::Triangle)
+}
+
+/// Captures the monitor `monitor_id` to an image of its physical size.
+pub fn capture_monitor(monitor_id: u32) -> Result<RgbaImage, CaptureError> {
+    let monitors = get_monitors();
+
+    let monitor = monitors
+        .iter()
+        .find(|monitor| monitor.id == monitor_id)
+        .ok_or(CaptureError::MonitorNotFound(monitor_id))?;
+
+    let width = monitor.size.width.round() as u32;
+
+    let height = monitor.size.height.round() as u32;
+
+    #[cfg(target_os = "windows")]
+    let image: Result<RgbaImage, CaptureError> = unimplemented!();
+
+    #[cfg(target_os = "linux")]
+    let image = crate::linux::capture::capture(&desktop::monitor_frame(monitor, &monitors));
+
+    #[cfg(target_os = "macos")]
+    let image = crate::macos::capture::capture_display(monitor_id);
+
+    Ok(fit(image?, width, height))
+}
+
+/// Captures `rect`, given in the coordinate space windows are positioned in (a top-left
+/// origin, logical points on macOS and physical pixels elsewhere). The image is in the
+/// physical pixels of the monitor `rect` overlaps the most.
+pub fn capture_rect(rect: Rect) -> Result<RgbaImage, CaptureError> {
+    let monitors = get_monitors();
+
+    let overlap = |frame: Rect| {
+        rect.intersection(&frame)
+            .map(|overlap| overlap.area())
+            .unwrap_or_default()
+    };
+
+    let monitor = monitors
+        .iter()
+        .filter(|monitor| overlap(desktop::monitor_frame(monitor, &monitors)) > 0.0)
+        .max_by(|a, b| {
+            overlap(desktop::monitor_frame(a, &monitors))
+                .total_cmp(&overlap(desktop::monitor_frame(b, &monitors)))
+        })
+        .ok_or(CaptureError::OutOfBounds)?;
+
+    #[cfg(target_os = "macos")]
+    let scale_factor = monitor.scale_factor;
+
+    #[cfg(not(target_os = "macos"))]
+    let scale_factor = {
+        let _ = monitor;
+
+        1.0
+    };
+
+    let width = (rect.width * scale_factor).round() as u32;
+
+    let height = (rect.height * scale_factor).round() as u32;
+
+    #[cfg(target_os = "windows")]
+    let image: Result<RgbaImage, CaptureError> = unimplemented!();
+
+    #[cfg(target_os = "linux")]
+    let image = crate::linux::capture::capture(&rect);
+
+    #[cfg(target_os = "macos")]
+    let image = crate::macos::capture::capture(&rect);
+
+    Ok(fit(image?, width, height))
+}
@@ -0,0 +1,90 @@
+#![cfg(test)]
+
+use image::{Rgba, RgbaImage};
+
+use super::{fit, to_rgba, PixelFormat};
+
+const BGRX: PixelFormat = PixelFormat {
+    bits_per_pixel: 32,
+    bytes_per_row: 8,
+    red_mask: 0x00ff0000,
+    green_mask: 0x0000ff00,
+    blue_mask: 0x000000ff,
+    big_endian: false,
+};
+
+#[test]
+fn it_converts_32_bit_little_endian_pixels() {
+    // Two rows of two pixels in B, G, R, X byte order, as X11 and CoreGraphics return them.
+    let data = [
+        0x00, 0x00, 0xff, 0x00, 0x00, 0xff, 0x00, 0x00, //
+        0xff, 0x00, 0x00, 0x00, 0x30, 0x20, 0x10, 0xff,
+    ];
+
+    let image = to_rgba(&data, 2, 2, &BGRX);
+
+    assert_eq!(image.get_pixel(0, 0), &Rgba([0xff, 0x00, 0x00, 0xff]));
+    assert_eq!(image.get_pixel(1, 0), &Rgba([0x00, 0xff, 0x00, 0xff]));
+    assert_eq!(image.get_pixel(0, 1), &Rgba([0x00, 0x00, 0xff, 0xff]));
+    assert_eq!(image.get_pixel(1, 1), &Rgba([0x10, 0x20, 0x30, 0xff]));
+}
+
+#[test]
+fn it_skips_row_padding_and_handles_big_endian() {
+    let format = PixelFormat {
+        bits_per_pixel: 32,
+        bytes_per_row: 12,
+        big_endian: true,
+        ..BGRX
+    };
+
+    let data = [
+        0x00, 0x10, 0x20, 0x30, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, //
+        0x00, 0x40, 0x50, 0x60, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+    ];
+
+    let image = to_rgba(&data, 1, 2, &format);
+
+    assert_eq!(image.get_pixel(0, 0), &Rgba([0x10, 0x20, 0x30, 0xff]));
+    assert_eq!(image.get_pixel(0, 1), &Rgba([0x40, 0x50, 0x60, 0xff]));
+}
+
+#[test]
+fn it_expands_16_bit_pixels() {
+    let format = PixelFormat {
+        bits_per_pixel: 16,
+        bytes_per_row: 4,
+        red_mask: 0xf800,
+        green_mask: 0x07e0,
+        blue_mask: 0x001f,
+        big_endian: false,
+    };
+
+    // White, then pure green in RGB565.
+    let data = [0xff, 0xff, 0xe0, 0x07];
+
+    let image = to_rgba(&data, 2, 1, &format);
+
+    assert_eq!(image.get_pixel(0, 0), &Rgba([0xff, 0xff, 0xff, 0xff]));
+    assert_eq!(image.get_pixel(1, 0), &Rgba([0x00, 0xff, 0x00, 0xff]));
+}
+
+#[test]
+fn it_leaves_missing_pixels_transparent() {
+    let image = to_rgba(&[0x00, 0x00, 0xff, 0x00], 2, 1, &BGRX);
+
+    assert_eq!(image.get_pixel(0, 0), &Rgba([0xff, 0x00, 0x00, 0xff]));
+    assert_eq!(image.get_pixel(1, 0), &Rgba([0x00, 0x00, 0x00, 0x00]));
+}
+
+#[test]
+fn it_fits_images_to_the_reported_size() {
+    let image = RgbaImage::from_pixel(2880, 1800, Rgba([10, 20, 30, 255]));
+
+    let fitted = fit(image.clone(), 1440, 900);
+
+    assert_eq!(fitted.dimensions(), (1440, 900));
+    assert_eq!(fitted.get_pixel(720, 450), &Rgba([10, 20, 30, 255]));
+
+    assert_eq!(fit(image, 2880, 1800).dimensions(), (2880, 1800));
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::PhysicalPosition;
+
+use crate::PollingStream;
+
+/// Samples the cursor on the thread of a `CursorStream`.
+pub(crate) trait CursorSampler {
+    fn position(&mut self) -> Option<PhysicalPosition<f64>>;
+
+    /// The id of the monitor under `position`, which is only asked for when it moves.
+    fn monitor_id(&mut self, position: PhysicalPosition<f64>) -> Option<u32>;
+}
+
+fn sampler() -> Option<Box<dyn CursorSampler>> {
+    #[cfg(target_os = "windows")]
+    {
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::cursor::X11CursorSampler::new()
+            .map(|sampler| Box::new(sampler) as Box<dyn CursorSampler>)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Some(Box::new(
+            crate::macos::cursor::AppKitCursorSampler::default(),
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CursorEvent {
+    position: PhysicalPosition<f64>,
+    monitor_id: Option<u32>,
+    monitor_changed: bool,
+}
+
+impl CursorEvent {
+    pub fn position(&self) -> PhysicalPosition<f64> {
+        self.position
+    }
+
+    pub fn monitor_id(&self) -> Option<u32> {
+        self.monitor_id
+    }
+
+    pub fn monitor_changed(&self) -> bool {
+        self.monitor_changed
+    }
+}
+
+/// Receives a `CursorEvent` every time the cursor moves. Sampling stops when the stream is dropped.
+pub type CursorStream = PollingStream<CursorEvent>;
+
+/// Samples the cursor position every `interval` and reports each movement.
+/// The first event carries the position at the time tracking started.
+pub fn track_cursor(interval: Duration) -> CursorStream {
+    PollingStream::spawn(interval, || {
+        let mut sampler = sampler()?;
+
+        let mut last_position = None;
+
+        let mut last_monitor_id = None;
+
+        Some(move || {
+            let position = sampler
+                .position()
+                .filter(|position| Some(*position) != last_position)?;
+
+            let monitor_id = sampler.monitor_id(position);
+
+            let event = CursorEvent {
+                position,
+                monitor_id,
+                monitor_changed: last_position.is_none() || monitor_id != last_monitor_id,
+            };
+
+            last_position = Some(position);
+
+            last_monitor_id = monitor_id;
+
+            Some(event)
+        })
+    })
+}
@@ -0,0 +1,135 @@
+//! Conversions between `Monitor` geometry and the coordinate space the windowing
+//! system positions windows in: a top-left origin, in logical points on macOS and
+//! in physical pixels everywhere else.
+
+use tauri::{PhysicalPosition, PhysicalSize, Runtime, WebviewWindow};
+
+use crate::{get_cursor_position, Monitor, Rect};
+
+#[cfg(target_os = "macos")]
+fn to_desktop(
+    position: PhysicalPosition<f64>,
+    size: PhysicalSize<f64>,
+    scale_factor: f64,
+    monitors: &[Monitor],
+) -> Rect {
+    from_appkit(position, size, scale_factor, monitors)
+}
+
+/// Converts an AppKit frame, scaled by `scale_factor`, to top-left points.
+pub(crate) fn from_appkit(
+    position: PhysicalPosition<f64>,
+    size: PhysicalSize<f64>,
+    scale_factor: f64,
+    monitors: &[Monitor],
+) -> Rect {
+    // AppKit frames grow upwards from the bottom-left corner of the primary screen.
+    let primary_height = monitors
+        .iter()
+        .find(|monitor| monitor.is_primary)
+        .map(|monitor| monitor.size.height / monitor.scale_factor)
+        .unwrap_or_default();
+
+    let width = size.width / scale_factor;
+
+    let height = size.height / scale_factor;
+
+    Rect::new(
+        position.x / scale_factor,
+        primary_height - position.y / scale_factor - height,
+        width,
+        height,
+    )
+}
+
+#[cfg(not(target_os = "macos"))]
+fn to_desktop(
+    position: PhysicalPosition<f64>,
+    size: PhysicalSize<f64>,
+    _scale_factor: f64,
+    _monitors: &[Monitor],
+) -> Rect {
+    Rect::new(position.x, position.y, size.width, size.height)
+}
+
+/// The full frame of `monitor`. `monitors` is the list it belongs to.
+pub(crate) fn monitor_frame(monitor: &Monitor, monitors: &[Monitor]) -> Rect {
+    to_desktop(
+        monitor.position,
+        monitor.size,
+        monitor.scale_factor,
+        monitors,
+    )
+}
+
+/// The frame of `monitor` that is not covered by the menubar, docks or panels.
+pub(crate) fn visible_frame(monitor: &Monitor, monitors: &[Monitor]) -> Rect {
+    to_desktop(
+        monitor.visible_area.position,
+        monitor.visible_area.size,
+        monitor.scale_factor,
+        monitors,
+    )
+}
+
+/// The position of the cursor, or `None` if it cannot be read.
+pub(crate) fn cursor_position(monitors: &[Monitor]) -> Option<(f64, f64)> {
+    let position = get_cursor_position()?;
+
+    // The cursor is reported in the physical pixels of the monitor under it.
+    #[cfg(target_os = "macos")]
+    let scale_factor = monitors
+        .iter()
+        .find(|monitor| monitor.has_cursor)
+        .map_or(1.0, |monitor| monitor.scale_factor);
+
+    #[cfg(not(target_os = "macos"))]
+    let scale_factor = {
+        let _ = monitors;
+
+        1.0
+    };
+
+    Some((position.x / scale_factor, position.y / scale_factor))
+}
+
+/// The window's outer position together with its inner size.
+pub(crate) fn window_frame<R: Runtime>(window: &WebviewWindow<R>) -> tauri::Result<Rect> {
+    let position = window.outer_position()?;
+
+    let size = window.inner_size()?;
+
+    #[cfg(target_os = "macos")]
+    let scale_factor = window.scale_factor()?;
+
+    #[cfg(not(target_os = "macos"))]
+    let scale_factor = 1.0;
+
+    Ok(Rect::new(
+        position.x as f64 / scale_factor,
+        position.y as f64 / scale_factor,
+        size.width as f64 / scale_factor,
+        size.height as f64 / scale_factor,
+    ))
+}
+
+pub(crate) fn set_window_frame<R: Runtime>(
+    window: &WebviewWindow<R>,
+    frame: &Rect,
+) -> tauri::Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        use tauri::{LogicalPosition, LogicalSize};
+
+        window.set_size(LogicalSize::new(frame.width, frame.height))?;
+
+        window.set_position(LogicalPosition::new(frame.x, frame.y))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        window.set_size(PhysicalSize::new(frame.width, frame.height))?;
+
+        window.set_position(PhysicalPosition::new(frame.x, frame.y))
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::{Mutex, Once};
+
+type Listener = Box<dyn Fn() -> bool + Send>;
+
+static LISTENERS: Mutex<Vec<Listener>> = Mutex::new(Vec::new());
+
+static WATCH: Once = Once::new();
+
+/// Calls `listener` after monitors are attached, detached or rearranged, until it
+/// returns `false`. The OS is only watched once a listener is added.
+pub(crate) fn on_displays_changed(listener: impl Fn() -> bool + Send + 'static) {
+    LISTENERS.lock().unwrap().push(Box::new(listener));
+
+    WATCH.call_once(watch);
+}
+
+/// Tells every listener that the display configuration changed.
+pub(crate) fn notify() {
+    LISTENERS.lock().unwrap().retain(|listener| listener());
+}
+
+fn watch() {
+    #[cfg(target_os = "windows")]
+    {
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::displays::watch()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::displays::watch()
+    }
+}
@@ -0,0 +1,25 @@
+#![cfg(test)]
+
+use tauri::{PhysicalPosition, PhysicalSize};
+
+use crate::{Monitor, Rect, VisibleArea};
+
+/// A monitor at `frame`, in physical pixels, with nothing covering it. The one with id
+/// 1 is the primary.
+pub(crate) fn monitor(id: u32, uuid: Option<&str>, frame: Rect, scale_factor: f64) -> Monitor {
+    Monitor {
+        id,
+        uuid: uuid.map(String::from),
+        name: None,
+        size: PhysicalSize::new(frame.width, frame.height),
+        position: PhysicalPosition::new(frame.x, frame.y),
+        scale_factor,
+        has_cursor: false,
+        is_primary: id == 1,
+        visible_area: VisibleArea {
+            size: PhysicalSize::new(frame.width, frame.height),
+            position: PhysicalPosition::new(frame.x, frame.y),
+            scale_factor,
+        },
+    }
+}
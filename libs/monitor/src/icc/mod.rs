@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+mod tests;
+
+const HEADER_SIZE: usize = 128;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum IccError {
+    #[error("the profile is shorter than its header")]
+    TooShort,
+    #[error("the profile does not have the 'acsp' signature")]
+    InvalidSignature,
+    #[error("the '{0}' tag is malformed")]
+    InvalidTag(String),
+}
+
+/// A few fields of an ICC profile, enough to tell profiles apart and pick a rendering path.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IccSummary {
+    /// The profile's `desc` tag, e.g. "Color LCD" or "Display P3".
+    pub description: Option<String>,
+    /// The data color space signature, e.g. "RGB".
+    pub color_space: String,
+    /// The profile class signature, "mntr" for displays.
+    pub class: String,
+    /// The profile version, e.g. "4.3".
+    pub version: String,
+    /// The CIEXYZ media white point from the `wtpt` tag, or the header illuminant without one.
+    pub white_point: [f64; 3],
+}
+
+/// The ICC color profile of a monitor.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IccProfile {
+    bytes: Vec<u8>,
+}
+
+impl IccProfile {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    /// The raw profile, e.g. to hand to a color management library.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn summary(&self) -> Result<IccSummary, IccError> {
+        let bytes = &self.bytes;
+
+        if bytes.len() < HEADER_SIZE + 4 {
+            return Err(IccError::TooShort);
+        }
+
+        if &bytes[36..40] != b"acsp" {
+            return Err(IccError::InvalidSignature);
+        }
+
+        let mut description = None;
+
+        let mut white_point = xyz(&bytes[68..80]);
+
+        let tag_count = u32_at(bytes, HEADER_SIZE) as usize;
+
+        for index in 0..tag_count {
+            let entry = HEADER_SIZE + 4 + index * 12;
+
+            if entry + 12 > bytes.len() {
+                return Err(IccError::TooShort);
+            }
+
+            let signature = signature(&bytes[entry..entry + 4]);
+
+            let offset = u32_at(bytes, entry + 4) as usize;
+
+            let size = u32_at(bytes, entry + 8) as usize;
+
+            let data = bytes
+                .get(offset..offset.saturating_add(size))
+                .ok_or_else(|| IccError::InvalidTag(signature.clone()))?;
+
+            match signature.as_str() {
+                "desc" => description = Some(text(data).ok_or(IccError::InvalidTag(signature))?),
+                "wtpt" if data.len() >= 20 && &data[0..4] == b"XYZ " => {
+                    white_point = xyz(&data[8..20])
+                }
+                _ => {}
+            }
+        }
+
+        Ok(IccSummary {
+            description,
+            color_space: signature(&bytes[16..20]),
+            class: signature(&bytes[12..16]),
+            version: format!("{}.{}", bytes[8], bytes[9] >> 4),
+            white_point,
+        })
+    }
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+fn signature(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).trim_end().to_string()
+}
+
+/// Decodes three `s15Fixed16Number`s.
+fn xyz(bytes: &[u8]) -> [f64; 3] {
+    let value = |index: usize| u32_at(bytes, index * 4) as i32 as f64 / 65536.0;
+
+    [value(0), value(1), value(2)]
+}
+
+/// Decodes a `textDescriptionType` (ICC v2) or the first record of a
+/// `multiLocalizedUnicodeType` (ICC v4).
+fn text(data: &[u8]) -> Option<String> {
+    match data.get(0..4)? {
+        b"desc" => {
+            let length = u32_at(data.get(0..12)?, 8) as usize;
+
+            let ascii = data.get(12..12 + length)?;
+
+            Some(
+                String::from_utf8_lossy(ascii)
+                    .trim_end_matches('\0')
+                    .to_string(),
+            )
+        }
+        b"mluc" => {
+            let record = data.get(16..28)?;
+
+            let length = u32_at(record, 4) as usize;
+
+            let offset = u32_at(record, 8) as usize;
+
+            let units = data
+                .get(offset..offset + length)?
+                .chunks_exact(2)
+                .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+                .collect::<Vec<u16>>();
+
+            Some(String::from_utf16_lossy(&units))
+        }
+        _ => None,
+    }
+}
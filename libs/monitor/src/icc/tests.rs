@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use super::{IccError, IccProfile};
+
+/// Builds a display profile with a `desc` tag of the given type and a D50 `wtpt` tag.
+fn profile(version: u8, desc: Vec<u8>) -> Vec<u8> {
+    let wtpt = [
+        b"XYZ ".to_vec(),
+        vec![0; 4],
+        0xf6d6u32.to_be_bytes().to_vec(),
+        0x10000u32.to_be_bytes().to_vec(),
+        0xd32du32.to_be_bytes().to_vec(),
+    ]
+    .concat();
+
+    let tags_offset = 128 + 4 + 2 * 12;
+
+    let mut bytes = vec![0u8; 128];
+
+    bytes[8] = version;
+
+    bytes[9] = 0x30;
+
+    bytes[12..16].copy_from_slice(b"mntr");
+
+    bytes[16..20].copy_from_slice(b"RGB ");
+
+    bytes[20..24].copy_from_slice(b"XYZ ");
+
+    bytes[36..40].copy_from_slice(b"acsp");
+
+    bytes.extend(2u32.to_be_bytes());
+
+    bytes.extend(b"desc");
+
+    bytes.extend((tags_offset as u32).to_be_bytes());
+
+    bytes.extend((desc.len() as u32).to_be_bytes());
+
+    bytes.extend(b"wtpt");
+
+    bytes.extend(((tags_offset + desc.len()) as u32).to_be_bytes());
+
+    bytes.extend((wtpt.len() as u32).to_be_bytes());
+
+    bytes.extend(desc);
+
+    bytes.extend(wtpt);
+
+    let size = bytes.len() as u32;
+
+    bytes[0..4].copy_from_slice(&size.to_be_bytes());
+
+    bytes
+}
+
+#[test]
+fn it_summarizes_a_v2_profile() {
+    let desc = [
+        b"desc".to_vec(),
+        vec![0; 4],
+        10u32.to_be_bytes().to_vec(),
+        b"Color LCD\0".to_vec(),
+    ]
+    .concat();
+
+    let summary = IccProfile::new(profile(2, desc)).summary().unwrap();
+
+    assert_eq!(summary.description.as_deref(), Some("Color LCD"));
+
+    assert_eq!(summary.color_space, "RGB");
+
+    assert_eq!(summary.class, "mntr");
+
+    assert_eq!(summary.version, "2.3");
+
+    assert!((summary.white_point[0] - 0.9642).abs() < 0.0001);
+
+    assert!((summary.white_point[1] - 1.0).abs() < 0.0001);
+
+    assert!((summary.white_point[2] - 0.8249).abs() < 0.0001);
+}
+
+#[test]
+fn it_summarizes_a_v4_profile() {
+    let text = "Display P3"
+        .encode_utf16()
+        .flat_map(|unit| unit.to_be_bytes())
+        .collect::<Vec<u8>>();
+
+    let desc = [
+        b"mluc".to_vec(),
+        vec![0; 4],
+        1u32.to_be_bytes().to_vec(),
+        12u32.to_be_bytes().to_vec(),
+        b"enUS".to_vec(),
+        (text.len() as u32).to_be_bytes().to_vec(),
+        28u32.to_be_bytes().to_vec(),
+        text,
+    ]
+    .concat();
+
+    let summary = IccProfile::new(profile(4, desc)).summary().unwrap();
+
+    assert_eq!(summary.description.as_deref(), Some("Display P3"));
+
+    assert_eq!(summary.version, "4.3");
+}
+
+#[test]
+fn it_rejects_invalid_profiles() {
+    assert_eq!(
+        IccProfile::new(vec![0; 64]).summary(),
+        Err(IccError::TooShort)
+    );
+
+    assert_eq!(
+        IccProfile::new(vec![0; 256]).summary(),
+        Err(IccError::InvalidSignature)
+    );
+
+    let mut truncated = profile(2, b"desc".to_vec());
+
+    truncated.truncate(140);
+
+    assert_eq!(
+        IccProfile::new(truncated).summary(),
+        Err(IccError::TooShort)
+    );
+
+    assert_eq!(
+        IccProfile::new(profile(2, b"desc".to_vec())).summary(),
+        Err(IccError::InvalidTag("desc".to_string()))
+    );
+}
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{desktop, get_monitors, Monitor, Rect};
+
+mod tests;
+
+/// Edges closer than this are considered touching, which absorbs rounding in scaled frames.
+const TOLERANCE: f64 = 1.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LayoutMonitor {
+    pub id: u32,
+    pub name: Option<String>,
+    pub frame: Rect,
+    /// The part of `frame` not covered by the menubar, docks or panels.
+    pub visible_frame: Rect,
+    pub is_primary: bool,
+}
+
+/// The segment along which monitor `from` touches monitor `to`, which lies in `direction`.
+/// `start` and `end` are y coordinates for `Left` and `Right`, and x coordinates for `Up` and `Down`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SharedEdge {
+    pub from: u32,
+    pub to: u32,
+    pub direction: Direction,
+    pub start: f64,
+    pub end: f64,
+}
+
+impl SharedEdge {
+    pub fn length(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// The arrangement of monitors on the virtual desktop, in the coordinate space windows are
+/// positioned in: a top-left origin, logical points on macOS and physical pixels elsewhere.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct MonitorLayout {
+    bounds: Rect,
+    monitors: Vec<LayoutMonitor>,
+    edges: Vec<SharedEdge>,
+}
+
+impl MonitorLayout {
+    pub fn new(monitors: &[Monitor]) -> Self {
+        Self::from_monitors(
+            monitors
+                .iter()
+                .map(|monitor| LayoutMonitor {
+                    id: monitor.id,
+                    name: monitor.name.clone(),
+                    frame: desktop::monitor_frame(monitor, monitors),
+                    visible_frame: desktop::visible_frame(monitor, monitors),
+                    is_primary: monitor.is_primary,
+                })
+                .collect(),
+        )
+    }
+
+    pub fn from_monitors(monitors: Vec<LayoutMonitor>) -> Self {
+        let bounds = monitors
+            .iter()
+            .map(|monitor| monitor.frame)
+            .reduce(|bounds, frame| {
+                let x = bounds.x.min(frame.x);
+
+                let y = bounds.y.min(frame.y);
+
+                Rect::new(
+                    x,
+                    y,
+                    bounds.right().max(frame.right()) - x,
+                    bounds.bottom().max(frame.bottom()) - y,
+                )
+            })
+            .unwrap_or_default();
+
+        let mut edges = vec![];
+
+        for a in &monitors {
+            for b in &monitors {
+                if a.id != b.id {
+                    edges.extend(shared_edge(a, b));
+                }
+            }
+        }
+
+        Self {
+            bounds,
+            monitors,
+            edges,
+        }
+    }
+
+    /// The smallest rectangle containing every monitor.
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn monitors(&self) -> &[LayoutMonitor] {
+        &self.monitors
+    }
+
+    /// Every shared edge, listed once from each side.
+    pub fn edges(&self) -> &[SharedEdge] {
+        &self.edges
+    }
+
+    /// The edges the monitor `id` shares with its neighbors.
+    pub fn neighbors(&self, id: u32) -> Vec<SharedEdge> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.from == id)
+            .copied()
+            .collect()
+    }
+
+    /// The monitor to move to from monitor `id` in `direction`: the adjacent monitor with the
+    /// longest shared edge, or else the closest monitor lying entirely beyond that side.
+    pub fn next(&self, id: u32, direction: Direction) -> Option<u32> {
+        let adjacent = self
+            .edges
+            .iter()
+            .filter(|edge| edge.from == id && edge.direction == direction)
+            .max_by(|a, b| a.length().total_cmp(&b.length()));
+
+        if let Some(edge) = adjacent {
+            return Some(edge.to);
+        }
+
+        let from = self.monitors.iter().find(|monitor| monitor.id == id)?.frame;
+
+        let (from_x, from_y) = from.center();
+
+        self.monitors
+            .iter()
+            .filter(|monitor| {
+                let frame = &monitor.frame;
+
+                match direction {
+                    Direction::Left => frame.right() <= from.x + TOLERANCE,
+                    Direction::Right => frame.x >= from.right() - TOLERANCE,
+                    Direction::Up => frame.bottom() <= from.y + TOLERANCE,
+                    Direction::Down => frame.y >= from.bottom() - TOLERANCE,
+                }
+            })
+            .min_by(|a, b| {
+                let distance = |frame: &Rect| {
+                    let (x, y) = frame.center();
+
+                    (x - from_x).hypot(y - from_y)
+                };
+
+                distance(&a.frame).total_cmp(&distance(&b.frame))
+            })
+            .map(|monitor| monitor.id)
+    }
+}
+
+fn shared_edge(a: &LayoutMonitor, b: &LayoutMonitor) -> Option<SharedEdge> {
+    let (a_frame, b_frame) = (&a.frame, &b.frame);
+
+    let touches = |a: f64, b: f64| (a - b).abs() <= TOLERANCE;
+
+    let (direction, start, end) = if touches(a_frame.right(), b_frame.x) {
+        (
+            Direction::Right,
+            a_frame.y.max(b_frame.y),
+            a_frame.bottom().min(b_frame.bottom()),
+        )
+    } else if touches(a_frame.x, b_frame.right()) {
+        (
+            Direction::Left,
+            a_frame.y.max(b_frame.y),
+            a_frame.bottom().min(b_frame.bottom()),
+        )
+    } else if touches(a_frame.bottom(), b_frame.y) {
+        (
+            Direction::Down,
+            a_frame.x.max(b_frame.x),
+            a_frame.right().min(b_frame.right()),
+        )
+    } else if touches(a_frame.y, b_frame.bottom()) {
+        (
+            Direction::Up,
+            a_frame.x.max(b_frame.x),
+            a_frame.right().min(b_frame.right()),
+        )
+    } else {
+        return None;
+    };
+
+    // Monitors that only meet at a corner share no edge.
+    if end - start <= TOLERANCE {
+        return None;
+    }
+
+    Some(SharedEdge {
+        from: a.id,
+        to: b.id,
+        direction,
+        start,
+        end,
+    })
+}
+
+/// Returns the arrangement of all connected monitors.
+pub fn get_layout() -> MonitorLayout {
+    MonitorLayout::new(&get_monitors())
+}
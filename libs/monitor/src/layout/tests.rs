@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use crate::Rect;
+
+use super::{Direction, LayoutMonitor, MonitorLayout, SharedEdge};
+
+fn monitor(id: u32, frame: Rect) -> LayoutMonitor {
+    LayoutMonitor {
+        id,
+        name: None,
+        frame,
+        visible_frame: frame,
+        is_primary: id == 1,
+    }
+}
+
+//   +------+
+//   |  3   |
+//   +--+---+----------+
+//      |  1  |   2    |
+//      +-----+        |
+//            +--------+
+//                 +---+
+//                 | 4 |
+//                 +---+
+fn layout() -> MonitorLayout {
+    MonitorLayout::from_monitors(vec![
+        monitor(1, Rect::new(0.0, 0.0, 1440.0, 900.0)),
+        monitor(2, Rect::new(1440.0, 0.0, 1920.0, 1080.0)),
+        monitor(3, Rect::new(-400.0, -1080.0, 1920.0, 1080.0)),
+        monitor(4, Rect::new(2800.0, 1200.0, 560.0, 400.0)),
+    ])
+}
+
+#[test]
+fn it_computes_the_virtual_desktop_bounds() {
+    assert_eq!(
+        layout().bounds(),
+        Rect::new(-400.0, -1080.0, 3760.0, 2680.0)
+    );
+}
+
+#[test]
+fn it_finds_shared_edges() {
+    let layout = layout();
+
+    assert_eq!(
+        layout.neighbors(1),
+        vec![
+            SharedEdge {
+                from: 1,
+                to: 2,
+                direction: Direction::Right,
+                start: 0.0,
+                end: 900.0,
+            },
+            SharedEdge {
+                from: 1,
+                to: 3,
+                direction: Direction::Up,
+                start: 0.0,
+                end: 1440.0,
+            },
+        ]
+    );
+
+    assert_eq!(
+        layout.neighbors(2),
+        vec![
+            SharedEdge {
+                from: 2,
+                to: 1,
+                direction: Direction::Left,
+                start: 0.0,
+                end: 900.0,
+            },
+            SharedEdge {
+                from: 2,
+                to: 3,
+                direction: Direction::Up,
+                start: 1440.0,
+                end: 1520.0,
+            },
+        ]
+    );
+
+    assert!(layout.neighbors(4).is_empty());
+}
+
+#[test]
+fn it_ignores_monitors_touching_at_a_corner() {
+    let layout = MonitorLayout::from_monitors(vec![
+        monitor(1, Rect::new(0.0, 0.0, 1920.0, 1080.0)),
+        monitor(2, Rect::new(1920.0, 1080.0, 1920.0, 1080.0)),
+    ]);
+
+    assert!(layout.edges().is_empty());
+}
+
+#[test]
+fn it_finds_the_next_monitor_in_a_direction() {
+    let layout = layout();
+
+    assert_eq!(layout.next(1, Direction::Right), Some(2));
+
+    assert_eq!(layout.next(1, Direction::Up), Some(3));
+
+    assert_eq!(layout.next(2, Direction::Left), Some(1));
+
+    assert_eq!(layout.next(3, Direction::Down), Some(1));
+
+    assert_eq!(layout.next(2, Direction::Down), Some(4));
+
+    assert_eq!(layout.next(4, Direction::Up), Some(2));
+
+    assert_eq!(layout.next(1, Direction::Left), None);
+
+    assert_eq!(layout.next(5, Direction::Left), None);
+}
+
+#[test]
+fn it_serializes_the_layout() {
+    let layout = MonitorLayout::from_monitors(vec![monitor(1, Rect::new(0.0, 0.0, 1.0, 1.0))]);
+
+    let json = serde_json::to_value(&layout).unwrap();
+
+    assert_eq!(json["bounds"]["width"], 1.0);
+
+    assert_eq!(json["monitors"][0]["id"], 1);
+
+    assert_eq!(
+        serde_json::from_value::<MonitorLayout>(json).unwrap(),
+        layout
+    );
+}
@@ -1,13 +1,54 @@
 use serde::{Deserialize, Serialize};
-use tauri::{PhysicalPosition, PhysicalSize};
+use tauri::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+
+#[cfg(target_os = "linux")]
+mod linux;
 
 #[cfg(target_os = "macos")]
 mod macos;
 
+mod capture;
+mod cursor;
+mod desktop;
+mod displays;
+mod fixtures;
+mod icc;
+mod layout;
+mod placement;
+mod profile;
+mod rect;
+mod scale;
+mod stream;
+mod window;
+
+#[cfg(target_os = "linux")]
+pub use linux::backlight::{Backlight, BacklightKind, Backlights, BrightnessError};
+
+pub use capture::{capture_monitor, capture_rect, CaptureError};
+pub use cursor::{track_cursor, CursorEvent, CursorStream};
+pub use icc::{IccError, IccProfile, IccSummary};
+pub use layout::{get_layout, Direction, LayoutMonitor, MonitorLayout, SharedEdge};
+pub use placement::{Anchor, Margin, Placement, PlacementError, PlacementMonitor};
+pub use profile::{clamp_to_nearest, fingerprint, DisplayProfiles, ProfileError, Restored};
+pub use rect::Rect;
+pub use scale::{
+    logical_to_physical, monitor_from_logical, monitor_from_physical, move_rect_to_monitor,
+    physical_to_logical,
+};
+pub use stream::PollingStream;
+pub use window::WebviewWindowExt;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VisibleArea {
     size: PhysicalSize<f64>,
     position: PhysicalPosition<f64>,
+    /// Defaults to 1.0 for visible areas serialized before it was added.
+    #[serde(default = "default_scale_factor")]
+    scale_factor: f64,
+}
+
+fn default_scale_factor() -> f64 {
+    1.0
 }
 
 impl VisibleArea {
@@ -18,6 +59,19 @@ impl VisibleArea {
     pub fn position(&self) -> PhysicalPosition<f64> {
         self.position
     }
+
+    /// The scale factor of the monitor the visible area belongs to.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    pub fn logical_size(&self) -> LogicalSize<f64> {
+        self.size.to_logical(self.scale_factor)
+    }
+
+    pub fn logical_position(&self) -> LogicalPosition<f64> {
+        self.position.to_logical(self.scale_factor)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -62,6 +116,24 @@ impl Monitor {
         self.scale_factor
     }
 
+    /// The size of the monitor in logical units, using its own scale factor.
+    pub fn logical_size(&self) -> LogicalSize<f64> {
+        self.size.to_logical(self.scale_factor)
+    }
+
+    /// The position of the monitor in logical units, using its own scale factor.
+    pub fn logical_position(&self) -> LogicalPosition<f64> {
+        self.position.to_logical(self.scale_factor)
+    }
+
+    /// Whether `position`, in the same coordinates as `position()`, lies on the monitor.
+    pub fn contains(&self, position: PhysicalPosition<f64>) -> bool {
+        position.x >= self.position.x
+            && position.y >= self.position.y
+            && position.x < self.position.x + self.size.width
+            && position.y < self.position.y + self.size.height
+    }
+
     pub fn has_cursor(&self) -> bool {
         self.has_cursor
     }
@@ -69,6 +141,15 @@ impl Monitor {
     pub fn is_primary(&self) -> bool {
         self.is_primary
     }
+
+    pub fn icc_profile(&self) -> Option<IccProfile> {
+        get_icc_profile(self.id)
+    }
+
+    /// Captures the monitor to an image of its physical size.
+    pub fn capture(&self) -> Result<image::RgbaImage, CaptureError> {
+        capture_monitor(self.id)
+    }
 }
 
 pub fn get_monitor_with_cursor() -> Option<Monitor> {
@@ -79,7 +160,7 @@ pub fn get_monitor_with_cursor() -> Option<Monitor> {
 
     #[cfg(target_os = "linux")]
     {
-        unimplemented!()
+        linux::monitor::get_monitor_with_cursor()
     }
 
     #[cfg(target_os = "macos")]
@@ -96,7 +177,7 @@ pub fn get_monitors() -> Vec<Monitor> {
 
     #[cfg(target_os = "linux")]
     {
-        unimplemented!()
+        linux::monitor::get_monitors()
     }
 
     #[cfg(target_os = "macos")]
@@ -104,3 +185,40 @@ pub fn get_monitors() -> Vec<Monitor> {
         macos::monitor::get_monitors()
     }
 }
+
+/// Returns the position of the cursor in global coordinates, with the origin at the
+/// top-left corner of the primary monitor, in the physical pixels of the monitor under it.
+pub fn get_cursor_position() -> Option<PhysicalPosition<f64>> {
+    #[cfg(target_os = "windows")]
+    {
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::cursor::get_cursor_position()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::cursor::get_cursor_position()
+    }
+}
+
+/// Returns the ICC color profile of the monitor `monitor_id`, if one is set.
+pub fn get_icc_profile(monitor_id: u32) -> Option<IccProfile> {
+    #[cfg(target_os = "windows")]
+    {
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::icc::get_icc_profile(monitor_id).map(IccProfile::new)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::icc::get_icc_profile(monitor_id).map(IccProfile::new)
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{get_monitors, Monitor};
+
+/// Where sysfs is mounted.
+pub const SYSFS_ROOT: &str = "/sys";
+
+/// Connector types of built-in panels, the only ones a backlight can drive.
+const INTERNAL_CONNECTORS: [&str; 3] = ["eDP", "LVDS", "DSI"];
+
+#[derive(Error, Debug)]
+pub enum BrightnessError {
+    #[error("permission denied for {0}; a udev rule must grant write access to the backlight")]
+    PermissionDenied(PathBuf),
+    #[error("failed to access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("{0} does not contain a brightness value")]
+    InvalidValue(PathBuf),
+    #[error("no backlight device named '{0}'")]
+    DeviceNotFound(String),
+    #[error("monitor {0} has no backlight")]
+    NoBacklight(u32),
+}
+
+impl BrightnessError {
+    fn from_io(path: &Path, error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied(path.to_path_buf()),
+            _ => Self::Io {
+                path: path.to_path_buf(),
+                source: error,
+            },
+        }
+    }
+}
+
+/// How a backlight device controls the panel, from most to least preferred.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum BacklightKind {
+    /// Through the firmware, e.g. `acpi_video0`.
+    Firmware,
+    /// Through a platform driver, e.g. a vendor's laptop driver.
+    Platform,
+    /// Through the GPU's registers, e.g. `intel_backlight`.
+    Raw,
+}
+
+/// A device under `/sys/class/backlight`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Backlight {
+    name: String,
+    kind: BacklightKind,
+    brightness: u32,
+    max_brightness: u32,
+    connector: Option<String>,
+    monitor_id: Option<u32>,
+}
+
+impl Backlight {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> BacklightKind {
+        self.kind
+    }
+
+    /// The current brightness, between 0 and `max_brightness`.
+    pub fn brightness(&self) -> u32 {
+        self.brightness
+    }
+
+    pub fn max_brightness(&self) -> u32 {
+        self.max_brightness
+    }
+
+    /// The current brightness, between 0.0 and 1.0.
+    pub fn level(&self) -> f64 {
+        if self.max_brightness == 0 {
+            return 0.0;
+        }
+
+        self.brightness as f64 / self.max_brightness as f64
+    }
+
+    /// The DRM connector of the panel, e.g. "eDP-1".
+    pub fn connector(&self) -> Option<&str> {
+        self.connector.as_deref()
+    }
+
+    /// The id of the `Monitor` showing the panel, if it is on.
+    pub fn monitor_id(&self) -> Option<u32> {
+        self.monitor_id
+    }
+}
+
+/// Reads and writes backlight devices under a sysfs root, which is `/sys` by
+/// default and can point at a fake tree in tests.
+#[derive(Debug, Clone)]
+pub struct Backlights {
+    root: PathBuf,
+}
+
+impl Default for Backlights {
+    fn default() -> Self {
+        Self::new(SYSFS_ROOT)
+    }
+}
+
+impl Backlights {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Lists all backlight devices, mapped to the connected monitors.
+    pub fn list(&self) -> Result<Vec<Backlight>, BrightnessError> {
+        self.list_for(&get_monitors())
+    }
+
+    /// Lists all backlight devices, mapped to `monitors`. Devices are sorted so that
+    /// the preferred one for a panel comes first; devices that can't be read are
+    /// left out.
+    pub fn list_for(&self, monitors: &[Monitor]) -> Result<Vec<Backlight>, BrightnessError> {
+        let class = self.root.join("class/backlight");
+
+        let entries = match fs::read_dir(&class) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(BrightnessError::from_io(&class, error)),
+        };
+
+        let mut names = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect::<Vec<String>>();
+
+        names.sort();
+
+        let mut backlights = names
+            .iter()
+            .filter_map(|name| self.read(name, monitors).ok())
+            .collect::<Vec<Backlight>>();
+
+        backlights.sort_by_key(|backlight| backlight.kind);
+
+        Ok(backlights)
+    }
+
+    /// Reads the backlight device `name`.
+    pub fn get(&self, name: &str) -> Result<Backlight, BrightnessError> {
+        self.read(name, &get_monitors())
+    }
+
+    /// Returns the preferred backlight of the monitor `monitor_id`.
+    pub fn for_monitor(&self, monitor_id: u32) -> Result<Backlight, BrightnessError> {
+        self.list()?
+            .into_iter()
+            .find(|backlight| backlight.monitor_id == Some(monitor_id))
+            .ok_or(BrightnessError::NoBacklight(monitor_id))
+    }
+
+    /// Sets the brightness of the device `name`, clamped to its `max_brightness`.
+    pub fn set_brightness(&self, name: &str, brightness: u32) -> Result<(), BrightnessError> {
+        let device = self.device(name)?;
+
+        let max_brightness = read_value(&device.join("max_brightness"))?;
+
+        let path = device.join("brightness");
+
+        fs::write(&path, brightness.min(max_brightness).to_string())
+            .map_err(|error| BrightnessError::from_io(&path, error))
+    }
+
+    /// Sets the brightness of the device `name` to `level`, between 0.0 and 1.0.
+    pub fn set_level(&self, name: &str, level: f64) -> Result<(), BrightnessError> {
+        let max_brightness = read_value(&self.device(name)?.join("max_brightness"))?;
+
+        let brightness = (level.clamp(0.0, 1.0) * max_brightness as f64).round() as u32;
+
+        self.set_brightness(name, brightness)
+    }
+
+    fn device(&self, name: &str) -> Result<PathBuf, BrightnessError> {
+        let device = self.root.join("class/backlight").join(name);
+
+        if name.contains('/') || !device.join("brightness").exists() {
+            return Err(BrightnessError::DeviceNotFound(name.to_string()));
+        }
+
+        Ok(device)
+    }
+
+    fn read(&self, name: &str, monitors: &[Monitor]) -> Result<Backlight, BrightnessError> {
+        let device = self.device(name)?;
+
+        // `actual_brightness` is what the hardware reports, which may lag behind
+        // or differ from the last value written to `brightness`.
+        let brightness = read_value(&device.join("actual_brightness"))
+            .or_else(|_| read_value(&device.join("brightness")))?;
+
+        let max_brightness = read_value(&device.join("max_brightness"))?;
+
+        let kind = match fs::read_to_string(device.join("type"))
+            .as_deref()
+            .map(str::trim)
+        {
+            Ok("firmware") => BacklightKind::Firmware,
+            Ok("platform") => BacklightKind::Platform,
+            _ => BacklightKind::Raw,
+        };
+
+        let connector = self.connector(name, &device);
+
+        let monitor_id = connector.as_deref().and_then(|connector| {
+            monitors
+                .iter()
+                .find(|monitor| {
+                    monitor
+                        .name
+                        .as_deref()
+                        .map_or(false, |name| is_same_connector(name, connector))
+                })
+                .map(|monitor| monitor.id)
+        });
+
+        Ok(Backlight {
+            name: name.to_string(),
+            kind,
+            brightness,
+            max_brightness,
+            connector,
+            monitor_id,
+        })
+    }
+
+    /// Finds the DRM connector of the panel the backlight drives. GPU drivers register
+    /// the backlight as a child of the connector, so its parent device is named like
+    /// `card0-eDP-1`. Firmware and platform backlights hang off ACPI or the platform
+    /// bus instead, and are attributed to the only connected built-in panel.
+    fn connector(&self, name: &str, device: &Path) -> Option<String> {
+        let parent = fs::canonicalize(device.join("device"))
+            .ok()
+            .and_then(|parent| {
+                parent
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .and_then(|parent| connector_name(&parent).map(String::from));
+
+        if parent.is_some() {
+            return parent;
+        }
+
+        let connectors = fs::read_dir(self.root.join("class/drm"))
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let directory = entry.file_name().to_string_lossy().into_owned();
+
+                connector_name(&directory).map(|connector| (entry.path(), connector.to_string()))
+            })
+            .collect::<Vec<(PathBuf, String)>>();
+
+        if let Some((_, connector)) = connectors.iter().find(|(path, _)| path.join(name).exists()) {
+            return Some(connector.clone());
+        }
+
+        let mut internal = connectors.into_iter().filter(|(path, connector)| {
+            INTERNAL_CONNECTORS
+                .iter()
+                .any(|prefix| connector.starts_with(prefix))
+                && fs::read_to_string(path.join("status"))
+                    .map_or(false, |status| status.trim() == "connected")
+        });
+
+        match (internal.next(), internal.next()) {
+            (Some((_, connector)), None) => Some(connector),
+            _ => None,
+        }
+    }
+}
+
+/// Splits `card0-eDP-1` into `eDP-1`. Returns `None` for cards and render nodes.
+fn connector_name(directory: &str) -> Option<&str> {
+    let (card, connector) = directory.split_once('-')?;
+
+    let index = card.strip_prefix("card")?;
+
+    if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(connector)
+}
+
+/// Whether the RandR output `output` is the DRM connector `connector`. The
+/// modesetting driver names outputs after connectors ("eDP-1", "DP-1-1"), and the
+/// intel driver drops the dash before the first index ("eDP1", "DP1-1").
+fn is_same_connector(output: &str, connector: &str) -> bool {
+    let output = connector_name(output).unwrap_or(output);
+
+    output == connector || output == without_index_dash(connector)
+}
+
+/// `connector` without the dash before its first index, so "DP-1-1" becomes
+/// "DP1-1" but never "DP11".
+fn without_index_dash(connector: &str) -> String {
+    let index = connector
+        .match_indices('-')
+        .map(|(index, _)| index)
+        .find(|&index| {
+            connector[index + 1..]
+                .bytes()
+                .next()
+                .map_or(false, |byte| byte.is_ascii_digit())
+        });
+
+    match index {
+        Some(index) => format!("{}{}", &connector[..index], &connector[index + 1..]),
+        None => connector.to_string(),
+    }
+}
+
+fn read_value(path: &Path) -> Result<u32, BrightnessError> {
+    fs::read_to_string(path)
+        .map_err(|error| BrightnessError::from_io(path, error))?
+        .trim()
+        .parse()
+        .map_err(|_| BrightnessError::InvalidValue(path.to_path_buf()))
+}
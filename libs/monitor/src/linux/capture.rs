@@ -0,0 +1,115 @@
+use image::{imageops, RgbaImage};
+use x11rb::{
+    connection::Connection,
+    protocol::xproto::{ConnectionExt, ImageFormat, ImageOrder},
+};
+
+use crate::{
+    capture::{to_rgba, CaptureError, PixelFormat},
+    Rect,
+};
+
+use super::{portal, utils::connect};
+
+/// Wayland compositors do not let clients read the screen, and XWayland only sees its
+/// own windows, so Wayland sessions go through the screenshot portal instead.
+fn is_wayland() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+        || std::env::var("XDG_SESSION_TYPE").map_or(false, |session| session == "wayland")
+}
+
+/// Captures `frame`, in root window coordinates. Parts of `frame` outside the
+/// screen are left transparent.
+///
+/// On X11 this is a single `GetImage` request rather than an MIT-SHM `ShmGetImage`.
+/// Shared memory only pays off when the same segment is reused for many grabs, while
+/// captures here are one-off; it also needs a segment created and attached through
+/// `libc` for every call, and fails for remote displays and some sandboxes, where
+/// `GetImage` still works.
+pub fn capture(frame: &Rect) -> Result<RgbaImage, CaptureError> {
+    if is_wayland() {
+        return portal::capture(frame);
+    }
+
+    let (conn, root) =
+        connect().ok_or_else(|| CaptureError::Failed("cannot connect to the X server".into()))?;
+
+    let setup = conn.setup();
+
+    let screen = setup
+        .roots
+        .iter()
+        .find(|screen| screen.root == root)
+        .ok_or_else(|| CaptureError::Failed("the root window has no screen".into()))?;
+
+    let bounds = Rect::new(
+        0.0,
+        0.0,
+        screen.width_in_pixels as f64,
+        screen.height_in_pixels as f64,
+    );
+
+    let area = frame
+        .intersection(&bounds)
+        .ok_or(CaptureError::OutOfBounds)?;
+
+    let (x, y) = (area.x.round() as i16, area.y.round() as i16);
+
+    let (width, height) = (area.width.round() as u16, area.height.round() as u16);
+
+    let reply = conn
+        .get_image(ImageFormat::Z_PIXMAP, root, x, y, width, height, u32::MAX)
+        .map_err(|error| CaptureError::Failed(error.to_string()))?
+        .reply()
+        .map_err(|error| CaptureError::Failed(error.to_string()))?;
+
+    let find_visual = |id| {
+        screen
+            .allowed_depths
+            .iter()
+            .flat_map(|depth| depth.visuals.iter())
+            .find(|visual| visual.visual_id == id)
+    };
+
+    let visual = find_visual(reply.visual)
+        .or_else(|| find_visual(screen.root_visual))
+        .ok_or_else(|| CaptureError::Failed("the root window has no visual".into()))?;
+
+    let pixmap_format = setup
+        .pixmap_formats
+        .iter()
+        .find(|format| format.depth == reply.depth)
+        .ok_or_else(|| CaptureError::Failed(format!("unsupported depth {}", reply.depth)))?;
+
+    let bits_per_pixel = pixmap_format.bits_per_pixel as usize;
+
+    let scanline_pad = pixmap_format.scanline_pad as usize;
+
+    let format = PixelFormat {
+        bits_per_pixel,
+        bytes_per_row: (width as usize * bits_per_pixel + scanline_pad - 1) / scanline_pad
+            * scanline_pad
+            / 8,
+        red_mask: visual.red_mask,
+        green_mask: visual.green_mask,
+        blue_mask: visual.blue_mask,
+        big_endian: setup.image_byte_order == ImageOrder::MSB_FIRST,
+    };
+
+    let image = to_rgba(&reply.data, width as u32, height as u32, &format);
+
+    if area == *frame {
+        return Ok(image);
+    }
+
+    let mut framed = RgbaImage::new(frame.width.round() as u32, frame.height.round() as u32);
+
+    imageops::replace(
+        &mut framed,
+        &image,
+        (area.x - frame.x).round() as i64,
+        (area.y - frame.y).round() as i64,
+    );
+
+    Ok(framed)
+}
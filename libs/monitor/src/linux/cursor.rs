@@ -0,0 +1,115 @@
+use tauri::PhysicalPosition;
+use x11rb::{
+    connection::Connection,
+    protocol::{
+        randr::ConnectionExt as RandrConnectionExt,
+        xproto::{ConnectionExt, Window},
+        Event,
+    },
+    rust_connection::RustConnection,
+};
+
+use crate::{cursor::CursorSampler, Rect};
+
+use super::{
+    monitor::monitor_id,
+    utils::{connect, select_randr_changes},
+};
+
+pub fn get_cursor_position() -> Option<PhysicalPosition<f64>> {
+    let (conn, root) = connect()?;
+
+    query_pointer(&conn, root)
+}
+
+fn query_pointer(conn: &RustConnection, root: Window) -> Option<PhysicalPosition<f64>> {
+    let pointer = conn.query_pointer(root).ok()?.reply().ok()?;
+
+    Some(PhysicalPosition {
+        x: pointer.root_x as f64,
+        y: pointer.root_y as f64,
+    })
+}
+
+/// Samples the pointer for a `CursorStream` over a single X connection, and finds the
+/// monitor under it from a layout that is only read again when RandR reports a change.
+///
+/// The pointer is queried once per interval rather than followed with XInput2 raw
+/// motion events: those arrive at the device's rate, often 1000 Hz, carry deltas rather
+/// than root coordinates, and would still leave the thread waking up to notice the
+/// stream being stopped. One `QueryPointer` round trip per interval coalesces movement
+/// to the rate the caller asked for.
+pub struct X11CursorSampler {
+    conn: RustConnection,
+    root: Window,
+    monitors: Vec<(u32, Rect)>,
+}
+
+impl X11CursorSampler {
+    pub fn new() -> Option<Self> {
+        let (conn, root) = connect()?;
+
+        select_randr_changes(&conn, root)?;
+
+        let mut sampler = Self {
+            conn,
+            root,
+            monitors: Vec::new(),
+        };
+
+        sampler.read_monitors();
+
+        Some(sampler)
+    }
+
+    fn read_monitors(&mut self) {
+        self.monitors = self
+            .conn
+            .randr_get_monitors(self.root, true)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| {
+                reply
+                    .monitors
+                    .iter()
+                    .map(|info| {
+                        let frame = Rect::new(
+                            info.x as f64,
+                            info.y as f64,
+                            info.width as f64,
+                            info.height as f64,
+                        );
+
+                        (monitor_id(info), frame)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+}
+
+impl CursorSampler for X11CursorSampler {
+    fn position(&mut self) -> Option<PhysicalPosition<f64>> {
+        let mut changed = false;
+
+        while let Ok(Some(event)) = self.conn.poll_for_event() {
+            changed |= matches!(
+                event,
+                Event::RandrScreenChangeNotify(_) | Event::RandrNotify(_)
+            );
+        }
+
+        if changed {
+            self.read_monitors();
+        }
+
+        query_pointer(&self.conn, self.root)
+    }
+
+    fn monitor_id(&mut self, position: PhysicalPosition<f64>) -> Option<u32> {
+        self.monitors
+            .iter()
+            .find(|(_, frame)| frame.contains(position.x, position.y))
+            .map(|(id, _)| *id)
+    }
+}
@@ -0,0 +1,31 @@
+use std::thread;
+
+use x11rb::{connection::Connection, protocol::Event};
+
+use crate::displays::notify;
+
+use super::utils::{connect, select_randr_changes};
+
+/// Reports RandR screen, CRTC and output changes from a thread of its own, which blocks on
+/// a dedicated X connection.
+pub fn watch() {
+    thread::spawn(|| {
+        let (conn, root) = match connect() {
+            Some(connection) => connection,
+            None => return,
+        };
+
+        if select_randr_changes(&conn, root).is_none() {
+            return;
+        }
+
+        while let Ok(event) = conn.wait_for_event() {
+            if matches!(
+                event,
+                Event::RandrScreenChangeNotify(_) | Event::RandrNotify(_)
+            ) {
+                notify();
+            }
+        }
+    });
+}
@@ -0,0 +1,56 @@
+use x11rb::protocol::{
+    randr::ConnectionExt as RandrConnectionExt,
+    xproto::{AtomEnum, ConnectionExt},
+};
+
+use super::{
+    monitor::monitor_id,
+    utils::{connect, get_atom},
+};
+
+/// Reads the profile colord or the display's calibration tool attached to the monitor:
+/// the `_ICC_PROFILE` property of its RandR output, or else the `_ICC_PROFILE`
+/// (first monitor) or `_ICC_PROFILE_<n>` (nth monitor) property of the root window.
+pub fn get_icc_profile(id: u32) -> Option<Vec<u8>> {
+    let (conn, root) = connect()?;
+
+    if let Some(atom) = get_atom(&conn, "_ICC_PROFILE") {
+        let output_profile = conn
+            .randr_get_output_property(id, atom, AtomEnum::ANY, 0, u32::MAX, false, false)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .filter(|reply| reply.format == 8 && reply.num_items > 0);
+
+        if let Some(reply) = output_profile {
+            return Some(reply.data);
+        }
+    }
+
+    let index = conn
+        .randr_get_monitors(root, true)
+        .ok()?
+        .reply()
+        .ok()?
+        .monitors
+        .iter()
+        .position(|info| monitor_id(info) == id)?;
+
+    let name = match index {
+        0 => "_ICC_PROFILE".to_string(),
+        index => format!("_ICC_PROFILE_{}", index),
+    };
+
+    let atom = get_atom(&conn, &name)?;
+
+    let reply = conn
+        .get_property(false, root, atom, AtomEnum::ANY, 0, u32::MAX)
+        .ok()?
+        .reply()
+        .ok()?;
+
+    if reply.format == 8 && !reply.value.is_empty() {
+        Some(reply.value)
+    } else {
+        None
+    }
+}
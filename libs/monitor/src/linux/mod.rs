@@ -0,0 +1,10 @@
+pub mod backlight;
+pub mod capture;
+pub mod cursor;
+pub mod displays;
+pub mod icc;
+pub mod monitor;
+pub mod portal;
+mod tests;
+mod utils;
+pub mod workarea;
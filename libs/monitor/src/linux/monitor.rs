@@ -0,0 +1,107 @@
+use tauri::{PhysicalPosition, PhysicalSize};
+use x11rb::protocol::{
+    randr::{ConnectionExt as RandrConnectionExt, MonitorInfo},
+    xproto::ConnectionExt,
+};
+
+use crate::{Monitor, Rect, VisibleArea};
+
+use super::{
+    utils::{connect, scale_factor},
+    workarea::WorkAreas,
+};
+
+pub fn get_monitor_with_cursor() -> Option<Monitor> {
+    get_monitors()
+        .into_iter()
+        .find(|monitor| monitor.has_cursor)
+}
+
+/// Monitors usually map to a single RandR output, whose id is stable for as long as
+/// the connector exists.
+pub fn monitor_id(info: &MonitorInfo) -> u32 {
+    info.outputs.first().copied().unwrap_or(info.name)
+}
+
+pub fn get_monitors() -> Vec<Monitor> {
+    let (conn, root) = match connect() {
+        Some(connection) => connection,
+        None => return vec![],
+    };
+
+    let monitors = match conn
+        .randr_get_monitors(root, true)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+    {
+        Some(reply) => reply.monitors,
+        None => return vec![],
+    };
+
+    let pointer = conn
+        .query_pointer(root)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok());
+
+    let scale_factor = scale_factor(&conn, root);
+
+    let work_areas = WorkAreas::read(&conn, root);
+
+    monitors
+        .iter()
+        .map(|info| {
+            let name = conn
+                .get_atom_name(info.name)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .map(|reply| String::from_utf8_lossy(&reply.name).into_owned());
+
+            let has_cursor = pointer.as_ref().map_or(false, |pointer| {
+                let x = pointer.root_x as i32 - info.x as i32;
+
+                let y = pointer.root_y as i32 - info.y as i32;
+
+                x >= 0 && y >= 0 && x < info.width as i32 && y < info.height as i32
+            });
+
+            let position = PhysicalPosition {
+                x: info.x as f64,
+                y: info.y as f64,
+            };
+
+            let size = PhysicalSize {
+                width: info.width as f64,
+                height: info.height as f64,
+            };
+
+            let visible_area = work_areas.visible_area(&Rect::new(
+                position.x,
+                position.y,
+                size.width,
+                size.height,
+            ));
+
+            Monitor {
+                id: monitor_id(info),
+                uuid: None,
+                name,
+                size,
+                position,
+                scale_factor,
+                has_cursor,
+                is_primary: info.primary,
+                visible_area: VisibleArea {
+                    size: PhysicalSize {
+                        width: visible_area.width,
+                        height: visible_area.height,
+                    },
+                    position: PhysicalPosition {
+                        x: visible_area.x,
+                        y: visible_area.y,
+                    },
+                    scale_factor,
+                },
+            }
+        })
+        .collect()
+}
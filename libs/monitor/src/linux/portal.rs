@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+use image::{imageops, RgbaImage};
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::{OwnedObjectPath, OwnedValue, Value},
+};
+
+use crate::{capture::CaptureError, get_layout, Rect};
+
+const DESTINATION: &str = "org.freedesktop.portal.Desktop";
+
+const PATH: &str = "/org/freedesktop/portal/desktop";
+
+/// How long to wait for the portal's response. The portal may ask the user for
+/// permission first, so this leaves them time to answer.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Numbers the captures of this process, so that concurrent ones wait on different
+/// request objects.
+static NEXT_CAPTURE: AtomicUsize = AtomicUsize::new(0);
+
+fn failed(error: zbus::Error) -> CaptureError {
+    CaptureError::Failed(error.to_string())
+}
+
+/// Converts a `file://` URI to a path, decoding percent-escapes.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?.as_bytes();
+
+    let mut decoded = Vec::with_capacity(encoded.len());
+
+    let mut index = 0;
+
+    while index < encoded.len() {
+        let escaped = match encoded[index] {
+            b'%' => encoded
+                .get(index + 1..index + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+
+                index += 3;
+            }
+            None => {
+                decoded.push(encoded[index]);
+
+                index += 1;
+            }
+        }
+    }
+
+    Some(PathBuf::from(String::from_utf8(decoded).ok()?))
+}
+
+fn screenshot_path(uri: &OwnedValue) -> Option<PathBuf> {
+    <&str>::try_from(uri).ok().and_then(uri_to_path)
+}
+
+/// Crops `frame` out of a screenshot of the whole desktop, whose extent in the
+/// coordinates monitors are reported in is `bounds`. The screenshot may be at a
+/// different density than `bounds`, e.g. when the compositor scales XWayland.
+pub fn crop(screenshot: &RgbaImage, frame: &Rect, bounds: &Rect) -> Option<RgbaImage> {
+    let area = frame.intersection(bounds)?;
+
+    let scale_x = screenshot.width() as f64 / bounds.width;
+
+    let scale_y = screenshot.height() as f64 / bounds.height;
+
+    let x = ((area.x - bounds.x) * scale_x).round() as u32;
+
+    let y = ((area.y - bounds.y) * scale_y).round() as u32;
+
+    let cropped = imageops::crop_imm(
+        screenshot,
+        x,
+        y,
+        (area.width * scale_x).round() as u32,
+        (area.height * scale_y).round() as u32,
+    )
+    .to_image();
+
+    if area == *frame {
+        return Some(cropped);
+    }
+
+    let mut framed = RgbaImage::new(
+        (frame.width * scale_x).round() as u32,
+        (frame.height * scale_y).round() as u32,
+    );
+
+    imageops::replace(
+        &mut framed,
+        &cropped,
+        ((area.x - frame.x) * scale_x).round() as i64,
+        ((area.y - frame.y) * scale_y).round() as i64,
+    );
+
+    Some(framed)
+}
+
+/// Takes a non-interactive screenshot through `org.freedesktop.portal.Screenshot`
+/// and crops `frame` out of it.
+pub fn capture(frame: &Rect) -> Result<RgbaImage, CaptureError> {
+    let conn = Connection::session().map_err(failed)?;
+
+    let token = format!(
+        "monitor_capture_{}_{}",
+        std::process::id(),
+        NEXT_CAPTURE.fetch_add(1, Ordering::Relaxed)
+    );
+
+    // The portal replies on a request object whose path is derived from our unique
+    // name and the token, so we can subscribe before calling and not miss the response.
+    let name = conn
+        .unique_name()
+        .map(|name| name.trim_start_matches(':').replace('.', "_"))
+        .ok_or_else(|| CaptureError::Failed("the bus connection has no name".into()))?;
+
+    let request = Proxy::new(
+        &conn,
+        DESTINATION,
+        format!("{}/request/{}/{}", PATH, name, token),
+        "org.freedesktop.portal.Request",
+    )
+    .map_err(failed)?;
+
+    let mut responses = request.receive_signal("Response").map_err(failed)?;
+
+    let screenshot = Proxy::new(
+        &conn,
+        DESTINATION,
+        PATH,
+        "org.freedesktop.portal.Screenshot",
+    )
+    .map_err(failed)?;
+
+    let mut options = HashMap::new();
+
+    options.insert("handle_token", Value::from(token.as_str()));
+
+    options.insert("interactive", Value::from(false));
+
+    let _: OwnedObjectPath = screenshot
+        .call("Screenshot", &("", options))
+        .map_err(failed)?;
+
+    // The blocking signal iterator cannot time out, so it is drained on another thread.
+    // If the portal answers after we gave up, that thread deletes the screenshot it saved.
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let response = match responses.next() {
+            Some(response) => response,
+            None => return,
+        };
+
+        if let Err(mpsc::SendError(late)) = sender.send(response) {
+            let path = late
+                .body()
+                .deserialize::<(u32, HashMap<String, OwnedValue>)>()
+                .ok()
+                .and_then(|(_, results)| results.get("uri").and_then(screenshot_path));
+
+            if let Some(path) = path {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    });
+
+    let response = match receiver.recv_timeout(RESPONSE_TIMEOUT) {
+        Ok(response) => response,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            return Err(CaptureError::Failed(format!(
+                "the portal did not respond within {} seconds",
+                RESPONSE_TIMEOUT.as_secs()
+            )))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            return Err(CaptureError::Failed("the portal did not respond".into()))
+        }
+    };
+
+    let (code, results): (u32, HashMap<String, OwnedValue>) =
+        response.body().deserialize().map_err(failed)?;
+
+    match code {
+        0 => {}
+        1 => return Err(CaptureError::Cancelled),
+        code => {
+            return Err(CaptureError::Failed(format!(
+                "the portal failed with response {}",
+                code
+            )))
+        }
+    }
+
+    let path = results
+        .get("uri")
+        .and_then(screenshot_path)
+        .ok_or_else(|| CaptureError::Failed("the portal returned no screenshot".into()))?;
+
+    let screenshot = image::open(&path)?.into_rgba8();
+
+    // The portal saves to the user's pictures folder, which is not ours to fill up.
+    let _ = std::fs::remove_file(&path);
+
+    crop(&screenshot, frame, &get_layout().bounds()).ok_or(CaptureError::OutOfBounds)
+}
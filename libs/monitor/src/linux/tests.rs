@@ -0,0 +1,619 @@
+#![cfg(test)]
+
+use std::{
+    fs,
+    os::unix::fs::{symlink, PermissionsExt},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use tauri::{PhysicalPosition, PhysicalSize};
+use x11rb::{
+    connection::Connection,
+    protocol::xproto::{AtomEnum, ConnectionExt, CreateWindowAux, PropMode, WindowClass},
+    wrapper::ConnectionExt as _,
+    COPY_DEPTH_FROM_PARENT, NONE,
+};
+
+use crate::{
+    capture_rect, get_cursor_position, get_icc_profile, get_monitor_with_cursor, track_cursor,
+    BacklightKind, Backlights, BrightnessError, Monitor, Rect, VisibleArea,
+};
+
+use super::{
+    monitor::get_monitors,
+    portal::{crop, uri_to_path},
+    utils::{connect, Atoms},
+    workarea::{apply_struts, Strut, WorkAreas},
+};
+
+// The pointer is shared by every test, so warping tests must not overlap.
+static POINTER: Mutex<()> = Mutex::new(());
+
+// These tests need an X server, e.g. `xvfb-run -s "-screen 0 1920x1080x24" cargo test`.
+fn warp_pointer(x: i16, y: i16) -> bool {
+    let (conn, root) = match connect() {
+        Some(connection) => connection,
+        None => return false,
+    };
+
+    conn.warp_pointer(NONE, root, 0, 0, 0, 0, x, y).unwrap();
+
+    conn.sync().unwrap();
+
+    true
+}
+
+#[test]
+fn it_gets_all_monitors() {
+    if connect().is_none() {
+        return;
+    }
+
+    let monitors = get_monitors();
+
+    assert!(!monitors.is_empty());
+}
+
+#[test]
+fn it_gets_the_cursor_position() {
+    let _lock = POINTER.lock().unwrap();
+
+    if !warp_pointer(120, 80) {
+        return;
+    }
+
+    assert_eq!(
+        get_cursor_position(),
+        Some(PhysicalPosition { x: 120.0, y: 80.0 })
+    );
+
+    let monitor = get_monitor_with_cursor().unwrap();
+
+    assert!(monitor.has_cursor());
+}
+
+#[test]
+fn it_tracks_cursor_movement() {
+    let _lock = POINTER.lock().unwrap();
+
+    if !warp_pointer(10, 10) {
+        return;
+    }
+
+    let stream = track_cursor(Duration::from_millis(5));
+
+    let first = stream.recv_timeout(Duration::from_secs(1)).unwrap();
+
+    assert_eq!(first.position(), PhysicalPosition { x: 10.0, y: 10.0 });
+
+    assert!(first.monitor_changed());
+
+    warp_pointer(200, 150);
+
+    let moved = stream.recv_timeout(Duration::from_secs(1)).unwrap();
+
+    assert_eq!(moved.position(), PhysicalPosition { x: 200.0, y: 150.0 });
+
+    assert_eq!(moved.monitor_id(), first.monitor_id());
+
+    assert!(!moved.monitor_changed());
+}
+
+#[test]
+fn it_only_shrinks_monitors_under_a_panel() {
+    let screen = Rect::new(0.0, 0.0, 3840.0, 1080.0);
+
+    let left = Rect::new(0.0, 0.0, 1920.0, 1080.0);
+
+    let right = Rect::new(1920.0, 0.0, 1920.0, 1080.0);
+
+    let top_panel = Strut {
+        top: 32.0,
+        top_start_x: 0.0,
+        top_end_x: 1919.0,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        apply_struts(&left, &screen, &[top_panel]),
+        Rect::new(0.0, 32.0, 1920.0, 1048.0)
+    );
+
+    assert_eq!(apply_struts(&right, &screen, &[top_panel]), right);
+}
+
+#[test]
+fn it_applies_struts_on_monitors_of_different_heights() {
+    let screen = Rect::new(0.0, 0.0, 4480.0, 1440.0);
+
+    let left = Rect::new(0.0, 0.0, 2560.0, 1440.0);
+
+    let right = Rect::new(2560.0, 0.0, 1920.0, 1080.0);
+
+    // A 48px dock along the bottom of the shorter monitor reserves from the bottom
+    // of the root window, which is 360px below it.
+    let dock = Strut {
+        bottom: 408.0,
+        bottom_start_x: 2560.0,
+        bottom_end_x: 4479.0,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        apply_struts(&right, &screen, &[dock]),
+        Rect::new(2560.0, 0.0, 1920.0, 1032.0)
+    );
+
+    assert_eq!(apply_struts(&left, &screen, &[dock]), left);
+}
+
+#[test]
+fn it_combines_struts_on_every_edge() {
+    let screen = Rect::new(0.0, 0.0, 1920.0, 1080.0);
+
+    let struts = [
+        Strut::from_values(&[0, 0, 27, 0], &screen).unwrap(),
+        Strut::from_values(&[64, 0, 0, 0, 27, 1079, 0, 0, 0, 0, 0, 0], &screen).unwrap(),
+        Strut::from_values(&[0, 10, 0, 40, 0, 0, 0, 1079, 0, 0, 0, 1919], &screen).unwrap(),
+    ];
+
+    assert_eq!(
+        apply_struts(&screen, &screen, &struts),
+        Rect::new(64.0, 27.0, 1846.0, 1013.0)
+    );
+}
+
+#[test]
+fn it_prefers_per_monitor_work_areas() {
+    let monitor = Rect::new(1920.0, 0.0, 1920.0, 1080.0);
+
+    let work_areas = WorkAreas {
+        screen: Rect::new(0.0, 0.0, 3840.0, 1080.0),
+        gtk_workareas: vec![
+            Rect::new(0.0, 32.0, 1920.0, 1048.0),
+            Rect::new(1920.0, 0.0, 1920.0, 1080.0),
+        ],
+        struts: vec![
+            Strut::from_values(&[0, 0, 32, 0], &Rect::new(0.0, 0.0, 3840.0, 1080.0)).unwrap(),
+        ],
+        net_workarea: Some(Rect::new(0.0, 32.0, 3840.0, 1048.0)),
+    };
+
+    assert_eq!(work_areas.visible_area(&monitor), monitor);
+
+    let work_areas = WorkAreas {
+        gtk_workareas: vec![],
+        ..work_areas
+    };
+
+    assert_eq!(
+        work_areas.visible_area(&monitor),
+        Rect::new(1920.0, 32.0, 1920.0, 1048.0)
+    );
+
+    let work_areas = WorkAreas {
+        struts: vec![],
+        ..work_areas
+    };
+
+    assert_eq!(
+        work_areas.visible_area(&monitor),
+        Rect::new(1920.0, 32.0, 1920.0, 1048.0)
+    );
+}
+
+#[test]
+fn it_excludes_dock_windows_from_the_visible_area() {
+    let (conn, root) = match connect() {
+        Some(connection) => connection,
+        None => return,
+    };
+
+    let atoms = Atoms::new(&conn).unwrap().reply().unwrap();
+
+    let dock = conn.generate_id().unwrap();
+
+    conn.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        dock,
+        root,
+        0,
+        0,
+        200,
+        40,
+        0,
+        WindowClass::INPUT_OUTPUT,
+        0,
+        &CreateWindowAux::new().override_redirect(1),
+    )
+    .unwrap();
+
+    conn.change_property32(
+        PropMode::REPLACE,
+        dock,
+        atoms._NET_WM_STRUT_PARTIAL,
+        AtomEnum::CARDINAL,
+        &[0, 0, 40, 0, 0, 0, 0, 0, 0, 199, 0, 0],
+    )
+    .unwrap();
+
+    conn.map_window(dock).unwrap();
+
+    conn.sync().unwrap();
+
+    let monitor = get_monitors()
+        .into_iter()
+        .find(|monitor| monitor.position().x == 0.0 && monitor.position().y == 0.0)
+        .unwrap();
+
+    conn.destroy_window(dock).unwrap();
+
+    conn.sync().unwrap();
+
+    assert_eq!(monitor.visible_area().position().y, 40.0);
+
+    assert_eq!(
+        monitor.visible_area().size().height,
+        monitor.size().height - 40.0
+    );
+}
+
+#[test]
+fn it_reads_the_icc_profile_of_the_first_monitor() {
+    let (conn, root) = match connect() {
+        Some(connection) => connection,
+        None => return,
+    };
+
+    let atom = conn
+        .intern_atom(false, b"_ICC_PROFILE")
+        .unwrap()
+        .reply()
+        .unwrap()
+        .atom;
+
+    let profile = b"not really a profile".to_vec();
+
+    conn.change_property8(PropMode::REPLACE, root, atom, AtomEnum::CARDINAL, &profile)
+        .unwrap();
+
+    conn.sync().unwrap();
+
+    let monitor = get_monitors().into_iter().next().unwrap();
+
+    let icc_profile = get_icc_profile(monitor.id());
+
+    conn.delete_property(root, atom).unwrap();
+
+    conn.sync().unwrap();
+
+    assert_eq!(icc_profile.unwrap().bytes(), profile.as_slice());
+}
+
+#[test]
+fn it_captures_a_window_on_screen() {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        return;
+    }
+
+    let (conn, root) = match connect() {
+        Some(connection) => connection,
+        None => return,
+    };
+
+    let window = conn.generate_id().unwrap();
+
+    conn.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        window,
+        root,
+        40,
+        30,
+        20,
+        10,
+        0,
+        WindowClass::INPUT_OUTPUT,
+        0,
+        &CreateWindowAux::new()
+            .override_redirect(1)
+            .background_pixel(0x00ff8000),
+    )
+    .unwrap();
+
+    conn.map_window(window).unwrap();
+
+    conn.sync().unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    let image = capture_rect(Rect::new(40.0, 30.0, 20.0, 10.0));
+
+    conn.destroy_window(window).unwrap();
+
+    conn.sync().unwrap();
+
+    let image = image.unwrap();
+
+    assert_eq!(image.dimensions(), (20, 10));
+
+    assert_eq!(image.get_pixel(10, 5).0, [0xff, 0x80, 0x00, 0xff]);
+}
+
+#[test]
+fn it_decodes_portal_uris() {
+    assert_eq!(
+        uri_to_path("file:///home/me/Pictures/Screenshot%20from%202024.png").unwrap(),
+        std::path::PathBuf::from("/home/me/Pictures/Screenshot from 2024.png")
+    );
+
+    assert!(uri_to_path("https://example.com/screenshot.png").is_none());
+}
+
+#[test]
+fn it_crops_scaled_portal_screenshots() {
+    // Two 100x50 monitors side by side, captured by a compositor at twice the density.
+    let mut screenshot = image::RgbaImage::new(400, 100);
+
+    for (x, _, pixel) in screenshot.enumerate_pixels_mut() {
+        *pixel = if x < 200 {
+            image::Rgba([255, 0, 0, 255])
+        } else {
+            image::Rgba([0, 0, 255, 255])
+        };
+    }
+
+    let bounds = Rect::new(0.0, 0.0, 200.0, 50.0);
+
+    let right = crop(&screenshot, &Rect::new(100.0, 0.0, 100.0, 50.0), &bounds).unwrap();
+
+    assert_eq!(right.dimensions(), (200, 100));
+
+    assert!(right.pixels().all(|pixel| pixel.0 == [0, 0, 255, 255]));
+
+    let overhanging = crop(&screenshot, &Rect::new(150.0, 0.0, 100.0, 50.0), &bounds).unwrap();
+
+    assert_eq!(overhanging.dimensions(), (200, 100));
+
+    assert_eq!(overhanging.get_pixel(50, 50).0, [0, 0, 255, 255]);
+
+    assert_eq!(overhanging.get_pixel(150, 50).0, [0, 0, 0, 0]);
+
+    assert!(crop(&screenshot, &Rect::new(300.0, 0.0, 10.0, 10.0), &bounds).is_none());
+}
+
+fn named_monitor(id: u32, name: &str) -> Monitor {
+    Monitor {
+        id,
+        uuid: None,
+        name: Some(name.to_string()),
+        size: PhysicalSize::new(1920.0, 1080.0),
+        position: PhysicalPosition::new(0.0, 0.0),
+        scale_factor: 1.0,
+        has_cursor: false,
+        is_primary: id == 1,
+        visible_area: VisibleArea {
+            size: PhysicalSize::new(1920.0, 1080.0),
+            position: PhysicalPosition::new(0.0, 0.0),
+            scale_factor: 1.0,
+        },
+    }
+}
+
+fn write_backlight(device: &Path, kind: &str, brightness: u32, max_brightness: u32) {
+    fs::create_dir_all(device).unwrap();
+
+    fs::write(device.join("type"), format!("{}\n", kind)).unwrap();
+
+    fs::write(device.join("brightness"), format!("{}\n", brightness)).unwrap();
+
+    fs::write(
+        device.join("actual_brightness"),
+        format!("{}\n", brightness),
+    )
+    .unwrap();
+
+    fs::write(
+        device.join("max_brightness"),
+        format!("{}\n", max_brightness),
+    )
+    .unwrap();
+}
+
+/// Builds a sysfs tree for a laptop with an internal panel on `card0-eDP-1`, driven by
+/// both `intel_backlight` and `acpi_video0`, and an external monitor on `card0-HDMI-A-1`.
+fn fake_sysfs(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("monitor-sysfs-{}-{}", std::process::id(), name));
+
+    let _ = fs::remove_dir_all(&root);
+
+    let card = root.join("devices/pci0000:00/0000:00:02.0/drm/card0");
+
+    fs::create_dir_all(root.join("class/backlight")).unwrap();
+
+    fs::create_dir_all(root.join("class/drm")).unwrap();
+
+    for connector in ["card0-eDP-1", "card0-HDMI-A-1"] {
+        fs::create_dir_all(card.join(connector)).unwrap();
+
+        fs::write(card.join(connector).join("status"), "connected\n").unwrap();
+
+        symlink(card.join(connector), root.join("class/drm").join(connector)).unwrap();
+    }
+
+    symlink(&card, root.join("class/drm/card0")).unwrap();
+
+    let intel = card.join("card0-eDP-1/intel_backlight");
+
+    write_backlight(&intel, "raw", 19200, 96000);
+
+    symlink("..", intel.join("device")).unwrap();
+
+    symlink(&intel, root.join("class/backlight/intel_backlight")).unwrap();
+
+    let acpi = root.join("devices/LNXSYSTM:00/LNXVIDEO:00/backlight/acpi_video0");
+
+    write_backlight(&acpi, "firmware", 5, 10);
+
+    symlink("../..", acpi.join("device")).unwrap();
+
+    symlink(&acpi, root.join("class/backlight/acpi_video0")).unwrap();
+
+    root
+}
+
+#[test]
+fn it_maps_backlights_to_monitors_via_drm_connectors() {
+    let root = fake_sysfs("map");
+
+    // The intel driver names outputs without the dash.
+    let monitors = [named_monitor(1, "eDP1"), named_monitor(2, "HDMI-A-1")];
+
+    let backlights = Backlights::new(&root).list_for(&monitors).unwrap();
+
+    let _ = fs::remove_dir_all(&root);
+
+    assert_eq!(backlights.len(), 2);
+
+    assert_eq!(backlights[0].name(), "acpi_video0");
+    assert_eq!(backlights[0].kind(), BacklightKind::Firmware);
+    assert_eq!(backlights[0].connector(), Some("eDP-1"));
+    assert_eq!(backlights[0].monitor_id(), Some(1));
+    assert_eq!(backlights[0].level(), 0.5);
+
+    assert_eq!(backlights[1].name(), "intel_backlight");
+    assert_eq!(backlights[1].kind(), BacklightKind::Raw);
+    assert_eq!(backlights[1].connector(), Some("eDP-1"));
+    assert_eq!(backlights[1].monitor_id(), Some(1));
+    assert_eq!(backlights[1].brightness(), 19200);
+    assert_eq!(backlights[1].max_brightness(), 96000);
+}
+
+#[test]
+fn it_leaves_backlights_of_disabled_panels_unmapped() {
+    let root = fake_sysfs("unmapped");
+
+    // With the lid closed, the panel has no monitor.
+    let backlights = Backlights::new(&root)
+        .list_for(&[named_monitor(2, "HDMI-A-1")])
+        .unwrap();
+
+    let _ = fs::remove_dir_all(&root);
+
+    assert!(backlights.iter().all(|backlight| {
+        backlight.connector() == Some("eDP-1") && backlight.monitor_id().is_none()
+    }));
+}
+
+#[test]
+fn it_matches_connectors_exactly() {
+    let root = fake_sysfs("exact");
+
+    // A second stream on the panel's port and a dash dropped anywhere but before the
+    // first index name other connectors.
+    let monitors = [named_monitor(1, "eDP-1-1"), named_monitor(2, "HDMIA1")];
+
+    let backlights = Backlights::new(&root).list_for(&monitors).unwrap();
+
+    let _ = fs::remove_dir_all(&root);
+
+    assert_eq!(backlights.len(), 2);
+
+    assert!(backlights
+        .iter()
+        .all(|backlight| backlight.monitor_id().is_none()));
+}
+
+#[test]
+fn it_skips_unreadable_backlights() {
+    let root = fake_sysfs("unreadable");
+
+    // A device whose driver never exposed `max_brightness`.
+    fs::create_dir_all(root.join("class/backlight/broken")).unwrap();
+
+    let backlights = Backlights::new(&root)
+        .list_for(&[named_monitor(1, "eDP-1")])
+        .unwrap();
+
+    let _ = fs::remove_dir_all(&root);
+
+    assert_eq!(
+        backlights
+            .iter()
+            .map(|backlight| backlight.name())
+            .collect::<Vec<_>>(),
+        ["acpi_video0", "intel_backlight"]
+    );
+}
+
+#[test]
+fn it_sets_brightness() {
+    let root = fake_sysfs("set");
+
+    let backlights = Backlights::new(&root);
+
+    let brightness = root.join("class/backlight/intel_backlight/brightness");
+
+    backlights.set_level("intel_backlight", 0.75).unwrap();
+
+    assert_eq!(fs::read_to_string(&brightness).unwrap(), "72000");
+
+    backlights
+        .set_brightness("intel_backlight", 200000)
+        .unwrap();
+
+    assert_eq!(fs::read_to_string(&brightness).unwrap(), "96000");
+
+    backlights.set_level("intel_backlight", -1.0).unwrap();
+
+    assert_eq!(fs::read_to_string(&brightness).unwrap(), "0");
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn it_reports_missing_backlights() {
+    let root = fake_sysfs("missing");
+
+    let backlights = Backlights::new(&root);
+
+    assert!(matches!(
+        backlights.set_level("amdgpu_bl0", 0.5),
+        Err(BrightnessError::DeviceNotFound(name)) if name == "amdgpu_bl0"
+    ));
+
+    assert!(matches!(
+        backlights.set_level("../../../etc", 0.5),
+        Err(BrightnessError::DeviceNotFound(_))
+    ));
+
+    let _ = fs::remove_dir_all(&root);
+
+    assert!(Backlights::new(root.join("nothing"))
+        .list_for(&[])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn it_reports_permission_errors() {
+    let root = fake_sysfs("permission");
+
+    let brightness = root.join("class/backlight/intel_backlight/brightness");
+
+    fs::set_permissions(&brightness, fs::Permissions::from_mode(0o444)).unwrap();
+
+    // Root ignores file permissions, so there is nothing to check.
+    if fs::OpenOptions::new().write(true).open(&brightness).is_ok() {
+        let _ = fs::remove_dir_all(&root);
+
+        return;
+    }
+
+    let result = Backlights::new(&root).set_level("intel_backlight", 0.5);
+
+    let _ = fs::remove_dir_all(&root);
+
+    assert!(matches!(result, Err(BrightnessError::PermissionDenied(path)) if path == brightness));
+}
@@ -0,0 +1,85 @@
+use x11rb::{
+    connection::Connection,
+    protocol::{
+        randr::{ConnectionExt as RandrConnectionExt, NotifyMask},
+        xproto::{Atom, AtomEnum, ConnectionExt, Window},
+    },
+    rust_connection::RustConnection,
+};
+
+x11rb::atom_manager! {
+    pub Atoms: AtomsCookie {
+        _NET_CLIENT_LIST,
+        _NET_CURRENT_DESKTOP,
+        _NET_WORKAREA,
+        _NET_WM_STRUT,
+        _NET_WM_STRUT_PARTIAL,
+    }
+}
+
+/// Connects to the X server named by `$DISPLAY` and returns the connection with its root window.
+pub fn connect() -> Option<(RustConnection, Window)> {
+    let (conn, screen_num) = x11rb::connect(None).ok()?;
+
+    let root = conn.setup().roots[screen_num].root;
+
+    Some((conn, root))
+}
+
+/// Asks RandR to report screen, CRTC and output changes on `conn`.
+pub fn select_randr_changes(conn: &RustConnection, root: Window) -> Option<()> {
+    // RandR only sends events to clients that have negotiated a version.
+    conn.randr_query_version(1, 5).ok()?.reply().ok()?;
+
+    conn.randr_select_input(
+        root,
+        NotifyMask::SCREEN_CHANGE | NotifyMask::CRTC_CHANGE | NotifyMask::OUTPUT_CHANGE,
+    )
+    .ok()?;
+
+    conn.flush().ok()
+}
+
+/// Reads the scale factor from the `Xft.dpi` X resource, which desktop environments
+/// set from their scaling setting. Defaults to `1.0`.
+pub fn scale_factor(conn: &RustConnection, root: Window) -> f64 {
+    let resources = conn
+        .get_property(
+            false,
+            root,
+            AtomEnum::RESOURCE_MANAGER,
+            AtomEnum::STRING,
+            0,
+            u32::MAX,
+        )
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .map(|reply| String::from_utf8_lossy(&reply.value).into_owned())
+        .unwrap_or_default();
+
+    resources
+        .lines()
+        .filter_map(|line| line.strip_prefix("Xft.dpi:"))
+        .filter_map(|dpi| dpi.trim().parse::<f64>().ok())
+        .map(|dpi| dpi / 96.0)
+        .next()
+        .unwrap_or(1.0)
+}
+
+/// Reads a `CARDINAL[]` or `WINDOW[]` property, returning an empty vector if it is not set.
+pub fn get_cardinals(conn: &RustConnection, window: Window, property: Atom) -> Vec<u32> {
+    conn.get_property(false, window, property, AtomEnum::ANY, 0, u32::MAX)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .and_then(|reply| reply.value32().map(|values| values.collect()))
+        .unwrap_or_default()
+}
+
+/// Interns `name` without creating it, returning `None` if no client has used it yet.
+pub fn get_atom(conn: &RustConnection, name: &str) -> Option<Atom> {
+    conn.intern_atom(true, name.as_bytes())
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .map(|reply| reply.atom)
+        .filter(|atom| *atom != x11rb::NONE)
+}
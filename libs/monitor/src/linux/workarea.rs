@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+
+use x11rb::{
+    connection::Connection,
+    cookie::Cookie,
+    protocol::xproto::{Atom, AtomEnum, ConnectionExt, GetPropertyReply, MapState, Window},
+    rust_connection::RustConnection,
+};
+
+use crate::Rect;
+
+use super::utils::{get_atom, get_cardinals, Atoms};
+
+/// Space reserved along the root window's edges by a panel or dock, as set in its
+/// `_NET_WM_STRUT_PARTIAL` property. The ranges are inclusive and in root coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Strut {
+    pub left: f64,
+    pub right: f64,
+    pub top: f64,
+    pub bottom: f64,
+    pub left_start_y: f64,
+    pub left_end_y: f64,
+    pub right_start_y: f64,
+    pub right_end_y: f64,
+    pub top_start_x: f64,
+    pub top_end_x: f64,
+    pub bottom_start_x: f64,
+    pub bottom_end_x: f64,
+}
+
+impl Strut {
+    /// Parses the 12 values of `_NET_WM_STRUT_PARTIAL`, or the 4 values of the older
+    /// `_NET_WM_STRUT` whose reservations span the whole `screen` edge.
+    pub fn from_values(values: &[u32], screen: &Rect) -> Option<Self> {
+        let values = values
+            .iter()
+            .map(|value| *value as f64)
+            .collect::<Vec<f64>>();
+
+        match values.len() {
+            12 => Some(Self {
+                left: values[0],
+                right: values[1],
+                top: values[2],
+                bottom: values[3],
+                left_start_y: values[4],
+                left_end_y: values[5],
+                right_start_y: values[6],
+                right_end_y: values[7],
+                top_start_x: values[8],
+                top_end_x: values[9],
+                bottom_start_x: values[10],
+                bottom_end_x: values[11],
+            }),
+            4 => Some(Self {
+                left: values[0],
+                right: values[1],
+                top: values[2],
+                bottom: values[3],
+                left_start_y: 0.0,
+                left_end_y: screen.height - 1.0,
+                right_start_y: 0.0,
+                right_end_y: screen.height - 1.0,
+                top_start_x: 0.0,
+                top_end_x: screen.width - 1.0,
+                bottom_start_x: 0.0,
+                bottom_end_x: screen.width - 1.0,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The rectangles reserved along the top, bottom, left and right edges of `screen`.
+    pub fn reserved(&self, screen: &Rect) -> [Rect; 4] {
+        [
+            Rect::new(
+                self.top_start_x,
+                screen.y,
+                self.top_end_x - self.top_start_x + 1.0,
+                self.top,
+            ),
+            Rect::new(
+                self.bottom_start_x,
+                screen.bottom() - self.bottom,
+                self.bottom_end_x - self.bottom_start_x + 1.0,
+                self.bottom,
+            ),
+            Rect::new(
+                screen.x,
+                self.left_start_y,
+                self.left,
+                self.left_end_y - self.left_start_y + 1.0,
+            ),
+            Rect::new(
+                screen.right() - self.right,
+                self.right_start_y,
+                self.right,
+                self.right_end_y - self.right_start_y + 1.0,
+            ),
+        ]
+    }
+}
+
+/// Shrinks `monitor` so that it excludes every strut reservation that overlaps it.
+/// A panel that only spans one monitor leaves the others untouched.
+pub fn apply_struts(monitor: &Rect, screen: &Rect, struts: &[Strut]) -> Rect {
+    let (mut left, mut top) = (monitor.x, monitor.y);
+
+    let (mut right, mut bottom) = (monitor.right(), monitor.bottom());
+
+    for strut in struts {
+        let [top_area, bottom_area, left_area, right_area] = strut.reserved(screen);
+
+        if top_area.intersection(monitor).is_some() {
+            top = top.max(top_area.bottom());
+        }
+
+        if bottom_area.intersection(monitor).is_some() {
+            bottom = bottom.min(bottom_area.y);
+        }
+
+        if left_area.intersection(monitor).is_some() {
+            left = left.max(left_area.right());
+        }
+
+        if right_area.intersection(monitor).is_some() {
+            right = right.min(right_area.x);
+        }
+    }
+
+    Rect::new(left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+}
+
+/// Everything the window manager and panels publish about the space they reserve.
+#[derive(Debug, Clone, Default)]
+pub struct WorkAreas {
+    pub screen: Rect,
+    /// Per-monitor work areas of the current desktop, from Mutter's `_GTK_WORKAREAS_D<n>`.
+    pub gtk_workareas: Vec<Rect>,
+    /// Struts of the mapped panels and docks.
+    pub struts: Vec<Strut>,
+    /// The work area of the current desktop spanning all monitors, from `_NET_WORKAREA`.
+    pub net_workarea: Option<Rect>,
+}
+
+impl WorkAreas {
+    pub fn read(conn: &RustConnection, root: Window) -> Self {
+        let setup_root = conn.setup().roots.iter().find(|screen| screen.root == root);
+
+        let screen = setup_root.map_or_else(Rect::default, |screen| {
+            Rect::new(
+                0.0,
+                0.0,
+                screen.width_in_pixels as f64,
+                screen.height_in_pixels as f64,
+            )
+        });
+
+        let atoms = match Atoms::new(conn).ok().and_then(|cookie| cookie.reply().ok()) {
+            Some(atoms) => atoms,
+            None => {
+                return Self {
+                    screen,
+                    ..Default::default()
+                }
+            }
+        };
+
+        let desktop = get_cardinals(conn, root, atoms._NET_CURRENT_DESKTOP)
+            .first()
+            .copied()
+            .unwrap_or_default();
+
+        let gtk_workareas = get_atom(conn, &format!("_GTK_WORKAREAS_D{}", desktop))
+            .map(|atom| to_rects(&get_cardinals(conn, root, atom)))
+            .unwrap_or_default();
+
+        let net_workarea = to_rects(&get_cardinals(conn, root, atoms._NET_WORKAREA))
+            .get(desktop as usize)
+            .copied();
+
+        let struts = panel_struts(conn, root, &atoms, &screen);
+
+        Self {
+            screen,
+            gtk_workareas,
+            struts,
+            net_workarea,
+        }
+    }
+
+    /// The part of `monitor` not covered by panels and docks. Mutter's per-monitor work
+    /// areas are preferred, then the panels' struts, and finally `_NET_WORKAREA`, which
+    /// is only accurate when panels span every monitor.
+    pub fn visible_area(&self, monitor: &Rect) -> Rect {
+        let overlap = |area: &&Rect| {
+            area.intersection(monitor)
+                .map(|overlap| overlap.area())
+                .unwrap_or_default()
+        };
+
+        if !self.gtk_workareas.is_empty() {
+            return self
+                .gtk_workareas
+                .iter()
+                .filter(|area| overlap(area) > 0.0)
+                .max_by(|a, b| overlap(a).total_cmp(&overlap(b)))
+                .and_then(|area| area.intersection(monitor))
+                .unwrap_or(*monitor);
+        }
+
+        if !self.struts.is_empty() {
+            return apply_struts(monitor, &self.screen, &self.struts);
+        }
+
+        self.net_workarea
+            .and_then(|area| area.intersection(monitor))
+            .unwrap_or(*monitor)
+    }
+}
+
+fn to_rects(values: &[u32]) -> Vec<Rect> {
+    values
+        .chunks_exact(4)
+        .map(|area| {
+            Rect::new(
+                area[0] as i32 as f64,
+                area[1] as i32 as f64,
+                area[2] as f64,
+                area[3] as f64,
+            )
+        })
+        .collect()
+}
+
+/// The struts of the mapped windows that reserve screen space. Window managers list
+/// docks in `_NET_CLIENT_LIST`; without one, panels are direct children of the root
+/// window. Only windows with a strut property take space, so no other window type
+/// needs checking.
+///
+/// Every request is sent before any reply is read, so the scan costs one round trip
+/// rather than three per window.
+fn panel_struts(conn: &RustConnection, root: Window, atoms: &Atoms, screen: &Rect) -> Vec<Strut> {
+    let mut windows = get_cardinals(conn, root, atoms._NET_CLIENT_LIST);
+
+    if let Some(tree) = conn
+        .query_tree(root)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+    {
+        windows.extend(tree.children);
+    }
+
+    let mut seen = HashSet::new();
+
+    windows.retain(|window| seen.insert(*window));
+
+    let property = |window: Window, property: Atom| {
+        conn.get_property(false, window, property, AtomEnum::CARDINAL, 0, 12)
+            .ok()
+    };
+
+    let cookies = windows
+        .into_iter()
+        .map(|window| {
+            (
+                conn.get_window_attributes(window).ok(),
+                property(window, atoms._NET_WM_STRUT_PARTIAL),
+                property(window, atoms._NET_WM_STRUT),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let values = |cookie: Option<Cookie<'_, RustConnection, GetPropertyReply>>| {
+        cookie
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| reply.value32().map(|values| values.collect::<Vec<u32>>()))
+            .unwrap_or_default()
+    };
+
+    cookies
+        .into_iter()
+        .filter_map(|(attributes, partial, strut)| {
+            let viewable = attributes
+                .and_then(|cookie| cookie.reply().ok())
+                .map_or(false, |attributes| {
+                    attributes.map_state == MapState::VIEWABLE
+                });
+
+            let (partial, strut) = (values(partial), values(strut));
+
+            if !viewable {
+                return None;
+            }
+
+            if partial.is_empty() {
+                Strut::from_values(&strut, screen)
+            } else {
+                Strut::from_values(&partial, screen)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,61 @@
+use core_graphics::{
+    display::{
+        kCGNullWindowID, kCGWindowImageDefault, kCGWindowListOptionOnScreenOnly, CGDisplay,
+        CGPoint, CGRect, CGSize,
+    },
+    image::CGImage,
+};
+use image::RgbaImage;
+
+use crate::{
+    capture::{to_rgba, CaptureError, PixelFormat},
+    Rect,
+};
+
+/// Screen images are 32-bit BGRA in host (little-endian) byte order.
+fn to_image(image: &CGImage) -> RgbaImage {
+    let data = image.data();
+
+    let format = PixelFormat {
+        bits_per_pixel: image.bits_per_pixel(),
+        bytes_per_row: image.bytes_per_row(),
+        red_mask: 0x00ff0000,
+        green_mask: 0x0000ff00,
+        blue_mask: 0x000000ff,
+        big_endian: false,
+    };
+
+    to_rgba(
+        data.bytes(),
+        image.width() as u32,
+        image.height() as u32,
+        &format,
+    )
+}
+
+pub fn capture_display(monitor_id: u32) -> Result<RgbaImage, CaptureError> {
+    let image = CGDisplay::new(monitor_id)
+        .image()
+        .ok_or_else(|| CaptureError::Failed("CGDisplayCreateImage returned no image".into()))?;
+
+    Ok(to_image(&image))
+}
+
+/// Captures `frame`, in points from the top-left corner of the primary display,
+/// which is the global display space of CoreGraphics.
+pub fn capture(frame: &Rect) -> Result<RgbaImage, CaptureError> {
+    let bounds = CGRect::new(
+        &CGPoint::new(frame.x, frame.y),
+        &CGSize::new(frame.width, frame.height),
+    );
+
+    let image = CGDisplay::screenshot(
+        bounds,
+        kCGWindowListOptionOnScreenOnly,
+        kCGNullWindowID,
+        kCGWindowImageDefault,
+    )
+    .ok_or_else(|| CaptureError::Failed("CGWindowListCreateImage returned no image".into()))?;
+
+    Ok(to_image(&image))
+}
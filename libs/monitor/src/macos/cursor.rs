@@ -0,0 +1,81 @@
+use cocoa::{
+    appkit::CGFloat,
+    base::id,
+    foundation::{NSPoint, NSRect, NSUInteger},
+};
+use objc::{
+    class, msg_send,
+    runtime::{NO, YES},
+    sel, sel_impl,
+};
+use tauri::PhysicalPosition;
+
+use crate::cursor::CursorSampler;
+
+use super::{monitor::NSMouseInRect, utils::screen_number};
+
+pub fn get_cursor_position() -> Option<PhysicalPosition<f64>> {
+    sample().map(|(position, _)| position)
+}
+
+/// Reads the mouse location once, and returns it with the id of the screen it is on.
+fn sample() -> Option<(PhysicalPosition<f64>, Option<u32>)> {
+    objc::rc::autoreleasepool(|| {
+        let mouse_location: NSPoint = unsafe { msg_send![class!(NSEvent), mouseLocation] };
+
+        let screens: id = unsafe { msg_send![class!(NSScreen), screens] };
+
+        let count: NSUInteger = unsafe { msg_send![screens, count] };
+
+        if count == 0 {
+            return None;
+        }
+
+        // The first screen holds the menubar, and its bottom-left corner is the origin
+        // of the global coordinate space.
+        let primary_screen: id = unsafe { msg_send![screens, objectAtIndex: 0] };
+
+        let primary_frame: NSRect = unsafe { msg_send![primary_screen, frame] };
+
+        let screen = (0..count)
+            .map(|index| -> id { unsafe { msg_send![screens, objectAtIndex: index] } })
+            .find(|screen| {
+                let frame: NSRect = unsafe { msg_send![*screen, frame] };
+
+                unsafe { NSMouseInRect(mouse_location, frame, NO) == YES }
+            });
+
+        let scale_factor = screen
+            .map(|screen| -> CGFloat { unsafe { msg_send![screen, backingScaleFactor] } })
+            .unwrap_or(1.0);
+
+        let position = PhysicalPosition {
+            x: mouse_location.x * scale_factor,
+            y: (primary_frame.size.height - mouse_location.y) * scale_factor,
+        };
+
+        Some((position, screen.map(screen_number)))
+    })
+}
+
+/// Samples the cursor for a `CursorStream`. AppKit keeps the screen list in the process,
+/// so there is nothing to cache between samples but the screen of the last one, which
+/// `monitor_id` reports so that the position and the monitor come from the same read.
+#[derive(Default)]
+pub struct AppKitCursorSampler {
+    last: Option<(PhysicalPosition<f64>, Option<u32>)>,
+}
+
+impl CursorSampler for AppKitCursorSampler {
+    fn position(&mut self) -> Option<PhysicalPosition<f64>> {
+        self.last = sample();
+
+        self.last.map(|(position, _)| position)
+    }
+
+    fn monitor_id(&mut self, position: PhysicalPosition<f64>) -> Option<u32> {
+        self.last
+            .filter(|(sampled, _)| *sampled == position)
+            .and_then(|(_, monitor_id)| monitor_id)
+    }
+}
@@ -0,0 +1,31 @@
+use std::{ffi::c_void, ptr};
+
+use core_graphics::display::CGDirectDisplayID;
+
+use crate::displays::notify;
+
+/// `kCGDisplayBeginConfigurationFlag`, set on the callback made before a reconfiguration.
+const BEGIN_CONFIGURATION_FLAG: u32 = 1 << 0;
+
+type ReconfigurationCallback = extern "C" fn(CGDirectDisplayID, u32, *mut c_void);
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGDisplayRegisterReconfigurationCallback(
+        callback: ReconfigurationCallback,
+        user_info: *mut c_void,
+    ) -> i32;
+}
+
+extern "C" fn reconfigured(_display: CGDirectDisplayID, flags: u32, _user_info: *mut c_void) {
+    // Each display is called back before and after the change; only the latter matters.
+    if flags & BEGIN_CONFIGURATION_FLAG == 0 {
+        notify();
+    }
+}
+
+/// Registers for display reconfigurations, which are reported on the main thread's run
+/// loop.
+pub fn watch() {
+    unsafe { CGDisplayRegisterReconfigurationCallback(reconfigured, ptr::null_mut()) };
+}
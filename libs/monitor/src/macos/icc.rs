@@ -0,0 +1,32 @@
+use core_foundation::{
+    base::{CFRelease, CFTypeRef, TCFType},
+    data::{CFData, CFDataRef},
+};
+use core_graphics::display::CGDirectDisplayID;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGDisplayCopyColorSpace(display: CGDirectDisplayID) -> CFTypeRef;
+
+    fn CGColorSpaceCopyICCData(space: CFTypeRef) -> CFDataRef;
+}
+
+pub fn get_icc_profile(monitor_id: u32) -> Option<Vec<u8>> {
+    let color_space = unsafe { CGDisplayCopyColorSpace(monitor_id) };
+
+    if color_space.is_null() {
+        return None;
+    }
+
+    let data_ref = unsafe { CGColorSpaceCopyICCData(color_space) };
+
+    unsafe { CFRelease(color_space) };
+
+    if data_ref.is_null() {
+        return None;
+    }
+
+    let data = unsafe { CFData::wrap_under_create_rule(data_ref) };
+
+    Some(data.bytes().to_vec())
+}
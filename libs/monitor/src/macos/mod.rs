@@ -1,3 +1,7 @@
+pub mod capture;
+pub mod cursor;
+pub mod displays;
+pub mod icc;
 pub mod monitor;
 mod tests;
 mod utils;
@@ -126,6 +126,7 @@ pub fn get_monitor_with_cursor() -> Option<Monitor> {
                         x: visible_frame.origin.x * scale_factor,
                         y: visible_frame.origin.y * scale_factor,
                     },
+                    scale_factor,
                 },
                 scale_factor,
                 has_cursor: true,
@@ -224,6 +225,7 @@ pub fn get_monitors() -> Vec<Monitor> {
                         x: visible_frame.origin.x * scale_factor,
                         y: visible_frame.origin.y * scale_factor,
                     },
+                    scale_factor,
                 },
                 scale_factor,
                 has_cursor: is_mouse_in_screen_frame == YES,
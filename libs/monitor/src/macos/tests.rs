@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use crate::get_monitor_with_cursor;
+use crate::{capture_rect, get_cursor_position, get_monitor_with_cursor, Rect};
 
 use super::monitor::get_monitors;
 
@@ -21,3 +21,52 @@ fn it_gets_all_monitors() {
 
     assert!(!monitors.is_empty());
 }
+
+#[test]
+fn it_gets_cursor_position() {
+    let position = get_cursor_position();
+
+    assert!(position.is_some());
+}
+
+#[test]
+fn it_gets_the_icc_profile() {
+    let monitor = get_monitor_with_cursor().unwrap();
+
+    let icc_profile = monitor.icc_profile();
+
+    assert!(icc_profile.is_some());
+
+    let summary = icc_profile.unwrap().summary().unwrap();
+
+    assert_eq!(summary.class, "mntr");
+}
+
+#[test]
+fn it_captures_monitors_at_their_physical_size() {
+    let monitor = get_monitor_with_cursor().unwrap();
+
+    let image = monitor.capture().unwrap();
+
+    assert_eq!(image.width() as f64, monitor.size().width.round());
+
+    assert_eq!(image.height() as f64, monitor.size().height.round());
+}
+
+#[test]
+fn it_captures_rects_in_physical_pixels() {
+    let monitor = get_monitors()
+        .into_iter()
+        .find(|monitor| monitor.is_primary())
+        .unwrap();
+
+    let image = capture_rect(Rect::new(10.0, 10.0, 100.0, 50.0)).unwrap();
+
+    assert_eq!(
+        image.dimensions(),
+        (
+            (100.0 * monitor.scale_factor()) as u32,
+            (50.0 * monitor.scale_factor()) as u32
+        )
+    );
+}
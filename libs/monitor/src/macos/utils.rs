@@ -1,7 +1,8 @@
-use std::ffi::{c_char, CStr};
+use std::ffi::{c_char, CStr, CString};
 
 use cocoa::base::id;
-use objc::{msg_send, sel, sel_impl};
+use core_graphics::display::CGDirectDisplayID;
+use objc::{class, msg_send, sel, sel_impl};
 
 pub fn nsstring_to_string(ns_string: id) -> Option<String> {
     let utf8: id = unsafe { msg_send![ns_string, UTF8String] };
@@ -18,3 +19,16 @@ pub fn nsstring_to_string(ns_string: id) -> Option<String> {
         None
     }
 }
+
+/// The display id of `screen`, from its `NSScreenNumber`.
+pub fn screen_number(screen: id) -> CGDirectDisplayID {
+    let key: id = unsafe {
+        msg_send![class!(NSString), stringWithCString: CString::new("NSScreenNumber").unwrap()]
+    };
+
+    let device_description: id = unsafe { msg_send![screen, deviceDescription] };
+
+    let number: id = unsafe { msg_send![device_description, objectForKey: key] };
+
+    unsafe { msg_send![number, unsignedIntValue] }
+}
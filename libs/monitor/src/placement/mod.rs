@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Rect;
+
+mod tests;
+
+#[derive(Error, Debug)]
+pub enum PlacementError {
+    #[error("failed to access the window: {0}")]
+    Window(#[from] tauri::Error),
+    #[error("no monitor matches the placement target")]
+    MonitorNotFound,
+}
+
+/// One of the nine points of a rectangle a window can be pinned to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+/// Spacing in logical units kept between a window and the edges of the visible area.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Margin {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+impl Margin {
+    pub fn all(value: f64) -> Self {
+        Self {
+            top: value,
+            right: value,
+            bottom: value,
+            left: value,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    /// Centers the window in the visible area.
+    Center,
+    /// Pins the window to `anchor`, keeping `margin` from the edges it touches.
+    Anchor { anchor: Anchor, margin: Margin },
+    /// Puts the window's top-left corner `offset` logical units away from the cursor,
+    /// flipping to the other side of the cursor when it would not fit.
+    NearCursor { offset: f64 },
+}
+
+/// The monitor a window is placed on.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementMonitor {
+    /// The monitor the window overlaps the most.
+    Current,
+    WithCursor,
+    Primary,
+    Id(u32),
+}
+
+impl Placement {
+    /// Computes the frame of a `width` by `height` window within `area`.
+    /// `scale_factor` converts the logical margins and offsets into the units of `area`,
+    /// and `cursor` is required by `Placement::NearCursor`, which otherwise centers the window.
+    pub fn frame(
+        &self,
+        area: &Rect,
+        width: f64,
+        height: f64,
+        scale_factor: f64,
+        cursor: Option<(f64, f64)>,
+    ) -> Rect {
+        let frame = match (*self, cursor) {
+            (Placement::Anchor { anchor, margin }, _) => {
+                let (left, right) = (margin.left * scale_factor, margin.right * scale_factor);
+
+                let (top, bottom) = (margin.top * scale_factor, margin.bottom * scale_factor);
+
+                let x = match anchor {
+                    Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => area.x + left,
+                    Anchor::Top | Anchor::Center | Anchor::Bottom => {
+                        area.x + left + (area.width - left - right - width) / 2.0
+                    }
+                    Anchor::TopRight | Anchor::Right | Anchor::BottomRight => {
+                        area.right() - right - width
+                    }
+                };
+
+                let y = match anchor {
+                    Anchor::TopLeft | Anchor::Top | Anchor::TopRight => area.y + top,
+                    Anchor::Left | Anchor::Center | Anchor::Right => {
+                        area.y + top + (area.height - top - bottom - height) / 2.0
+                    }
+                    Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => {
+                        area.bottom() - bottom - height
+                    }
+                };
+
+                Rect::new(x, y, width, height)
+            }
+            (Placement::NearCursor { offset }, Some((cursor_x, cursor_y))) => {
+                let offset = offset * scale_factor;
+
+                let mut x = cursor_x + offset;
+
+                let mut y = cursor_y + offset;
+
+                if x + width > area.right() {
+                    x = cursor_x - offset - width;
+                }
+
+                if y + height > area.bottom() {
+                    y = cursor_y - offset - height;
+                }
+
+                Rect::new(x, y, width, height)
+            }
+            _ => Rect::new(
+                area.x + (area.width - width) / 2.0,
+                area.y + (area.height - height) / 2.0,
+                width,
+                height,
+            ),
+        };
+
+        frame.clamp_to(area)
+    }
+}
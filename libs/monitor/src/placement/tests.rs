@@ -0,0 +1,97 @@
+#![cfg(test)]
+
+use crate::Rect;
+
+use super::{Anchor, Margin, Placement};
+
+const AREA: Rect = Rect {
+    x: 0.0,
+    y: 50.0,
+    width: 2000.0,
+    height: 1000.0,
+};
+
+#[test]
+fn it_centers_the_window() {
+    let frame = Placement::Center.frame(&AREA, 400.0, 200.0, 1.0, None);
+
+    assert_eq!(frame, Rect::new(800.0, 450.0, 400.0, 200.0));
+}
+
+#[test]
+fn it_anchors_to_every_corner_and_edge() {
+    let margin = Margin::all(10.0);
+
+    let frame = |anchor| Placement::Anchor { anchor, margin }.frame(&AREA, 400.0, 200.0, 1.0, None);
+
+    assert_eq!(frame(Anchor::TopLeft), Rect::new(10.0, 60.0, 400.0, 200.0));
+
+    assert_eq!(frame(Anchor::Top), Rect::new(800.0, 60.0, 400.0, 200.0));
+
+    assert_eq!(
+        frame(Anchor::TopRight),
+        Rect::new(1590.0, 60.0, 400.0, 200.0)
+    );
+
+    assert_eq!(frame(Anchor::Left), Rect::new(10.0, 450.0, 400.0, 200.0));
+
+    assert_eq!(frame(Anchor::Center), Rect::new(800.0, 450.0, 400.0, 200.0));
+
+    assert_eq!(frame(Anchor::Right), Rect::new(1590.0, 450.0, 400.0, 200.0));
+
+    assert_eq!(
+        frame(Anchor::BottomLeft),
+        Rect::new(10.0, 840.0, 400.0, 200.0)
+    );
+
+    assert_eq!(frame(Anchor::Bottom), Rect::new(800.0, 840.0, 400.0, 200.0));
+
+    assert_eq!(
+        frame(Anchor::BottomRight),
+        Rect::new(1590.0, 840.0, 400.0, 200.0)
+    );
+}
+
+#[test]
+fn it_scales_margins_to_the_monitor() {
+    let placement = Placement::Anchor {
+        anchor: Anchor::TopRight,
+        margin: Margin {
+            top: 8.0,
+            right: 12.0,
+            bottom: 0.0,
+            left: 0.0,
+        },
+    };
+
+    let frame = placement.frame(&AREA, 400.0, 200.0, 2.0, None);
+
+    assert_eq!(frame, Rect::new(1576.0, 66.0, 400.0, 200.0));
+}
+
+#[test]
+fn it_places_the_window_near_the_cursor() {
+    let placement = Placement::NearCursor { offset: 16.0 };
+
+    let frame = placement.frame(&AREA, 400.0, 200.0, 1.0, Some((100.0, 100.0)));
+
+    assert_eq!(frame, Rect::new(116.0, 116.0, 400.0, 200.0));
+}
+
+#[test]
+fn it_flips_away_from_edges_near_the_cursor() {
+    let placement = Placement::NearCursor { offset: 16.0 };
+
+    let frame = placement.frame(&AREA, 400.0, 200.0, 1.0, Some((1900.0, 1000.0)));
+
+    assert_eq!(frame, Rect::new(1484.0, 784.0, 400.0, 200.0));
+}
+
+#[test]
+fn it_keeps_the_window_inside_the_visible_area() {
+    let placement = Placement::NearCursor { offset: 0.0 };
+
+    let frame = placement.frame(&AREA, 2400.0, 200.0, 1.0, Some((10.0, 0.0)));
+
+    assert_eq!(frame, Rect::new(0.0, 50.0, 2000.0, 200.0));
+}
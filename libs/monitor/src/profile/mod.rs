@@ -0,0 +1,165 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use thiserror::Error;
+
+use crate::{Monitor, Rect};
+
+mod tests;
+
+#[derive(Error, Debug)]
+pub enum ProfileError {
+    #[error("failed to read or write the profiles file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse the profiles file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("failed to access the window: {0}")]
+    Window(#[from] tauri::Error),
+    #[error("no monitors are connected")]
+    NoMonitors,
+}
+
+/// How a window was placed by `restore_display_profile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Restored {
+    /// The window was moved to the frame saved for the current display arrangement.
+    Profile,
+    /// No frame was saved for the current display arrangement, so the window was moved onto the nearest monitor.
+    Clamped,
+    /// No frame was saved and the window is already on a monitor.
+    Unchanged,
+}
+
+type Profiles = BTreeMap<String, BTreeMap<String, Rect>>;
+
+/// Window frames keyed by display arrangement fingerprint and window label, persisted to a JSON file.
+pub struct DisplayProfiles {
+    path: PathBuf,
+    profiles: Mutex<Profiles>,
+}
+
+impl DisplayProfiles {
+    /// Loads the profiles stored at `path`. A missing file yields an empty set of profiles.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ProfileError> {
+        let path = path.into();
+
+        let profiles = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => Profiles::default(),
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(Self {
+            path,
+            profiles: Mutex::new(profiles),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the frame saved for the window `label` under the arrangement `fingerprint`.
+    pub fn get(&self, fingerprint: &str, label: &str) -> Option<Rect> {
+        self.profiles
+            .lock()
+            .unwrap()
+            .get(fingerprint)
+            .and_then(|windows| windows.get(label))
+            .copied()
+    }
+
+    /// Saves the frame of the window `label` under the arrangement `fingerprint` and writes the file.
+    pub fn insert(&self, fingerprint: &str, label: &str, frame: Rect) -> Result<(), ProfileError> {
+        let mut profiles = self.profiles.lock().unwrap();
+
+        let windows = profiles.entry(fingerprint.to_string()).or_default();
+
+        if windows.get(label) == Some(&frame) {
+            return Ok(());
+        }
+
+        windows.insert(label.to_string(), frame);
+
+        Self::write(&self.path, &profiles)
+    }
+
+    /// Writes all profiles to the file.
+    pub fn save(&self) -> Result<(), ProfileError> {
+        Self::write(&self.path, &self.profiles.lock().unwrap())
+    }
+
+    fn write(path: &Path, profiles: &Profiles) -> Result<(), ProfileError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(profiles)?)?;
+
+        Ok(())
+    }
+}
+
+/// Identifies a display arrangement from the monitors' uuids (or ids) and geometry.
+/// The result does not depend on the order of `monitors`.
+pub fn fingerprint(monitors: &[Monitor]) -> String {
+    let mut keys = monitors
+        .iter()
+        .map(|monitor| {
+            format!(
+                "{}:{},{}:{}x{}@{}",
+                monitor
+                    .uuid
+                    .clone()
+                    .unwrap_or_else(|| monitor.id.to_string()),
+                monitor.position.x.round() as i64,
+                monitor.position.y.round() as i64,
+                monitor.size.width.round() as i64,
+                monitor.size.height.round() as i64,
+                monitor.scale_factor,
+            )
+        })
+        .collect::<Vec<String>>();
+
+    keys.sort();
+
+    // FNV-1a, which unlike `DefaultHasher` is stable across Rust releases.
+    let hash = keys
+        .join(";")
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+
+    format!("{:016x}", hash)
+}
+
+/// Moves `frame` onto the monitor it overlaps the most, or onto the closest one
+/// when it is entirely off-screen. Returns `frame` unchanged when `monitors` is empty.
+pub fn clamp_to_nearest(frame: &Rect, monitors: &[Rect]) -> Rect {
+    let (center_x, center_y) = frame.center();
+
+    let nearest = monitors.iter().max_by(|a, b| {
+        let overlap = |monitor: &Rect| {
+            frame
+                .intersection(monitor)
+                .map(|overlap| overlap.area())
+                .unwrap_or_default()
+        };
+
+        overlap(a).total_cmp(&overlap(b)).then_with(|| {
+            b.distance_to(center_x, center_y)
+                .total_cmp(&a.distance_to(center_x, center_y))
+        })
+    });
+
+    match nearest {
+        Some(monitor) => frame.clamp_to(monitor),
+        None => *frame,
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(test)]
+
+use crate::{fixtures::monitor, Rect};
+
+use super::{clamp_to_nearest, fingerprint, DisplayProfiles};
+
+#[test]
+fn it_fingerprints_regardless_of_monitor_order() {
+    let laptop = monitor(1, Some("A"), Rect::new(0.0, 0.0, 2880.0, 1800.0), 2.0);
+
+    let external = monitor(2, Some("B"), Rect::new(2880.0, 0.0, 2560.0, 1440.0), 1.0);
+
+    assert_eq!(
+        fingerprint(&[laptop.clone(), external.clone()]),
+        fingerprint(&[external, laptop])
+    );
+}
+
+#[test]
+fn it_fingerprints_arrangement_changes() {
+    let laptop = monitor(1, Some("A"), Rect::new(0.0, 0.0, 2880.0, 1800.0), 2.0);
+
+    let right = monitor(2, Some("B"), Rect::new(2880.0, 0.0, 2560.0, 1440.0), 1.0);
+
+    let left = monitor(2, Some("B"), Rect::new(-2560.0, 0.0, 2560.0, 1440.0), 1.0);
+
+    let docked = fingerprint(&[laptop.clone(), right]);
+
+    assert_ne!(docked, fingerprint(std::slice::from_ref(&laptop)));
+
+    assert_ne!(docked, fingerprint(&[laptop, left]));
+}
+
+#[test]
+fn it_clamps_to_the_most_overlapped_monitor() {
+    let monitors = [
+        Rect::new(0.0, 0.0, 1920.0, 1080.0),
+        Rect::new(1920.0, 0.0, 1920.0, 1080.0),
+    ];
+
+    let frame = Rect::new(1800.0, 100.0, 400.0, 300.0);
+
+    assert_eq!(
+        clamp_to_nearest(&frame, &monitors),
+        Rect::new(1920.0, 100.0, 400.0, 300.0)
+    );
+}
+
+#[test]
+fn it_clamps_offscreen_frames_to_the_closest_monitor() {
+    let monitors = [
+        Rect::new(0.0, 0.0, 1920.0, 1080.0),
+        Rect::new(1920.0, 0.0, 1920.0, 1080.0),
+    ];
+
+    let frame = Rect::new(5000.0, 2000.0, 800.0, 600.0);
+
+    assert_eq!(
+        clamp_to_nearest(&frame, &monitors),
+        Rect::new(3040.0, 480.0, 800.0, 600.0)
+    );
+}
+
+#[test]
+fn it_shrinks_frames_larger_than_the_monitor() {
+    let monitors = [Rect::new(0.0, 25.0, 1440.0, 875.0)];
+
+    let frame = Rect::new(-100.0, 0.0, 2000.0, 1000.0);
+
+    assert_eq!(
+        clamp_to_nearest(&frame, &monitors),
+        Rect::new(0.0, 25.0, 1440.0, 875.0)
+    );
+}
+
+#[test]
+fn it_persists_profiles() {
+    let path = std::env::temp_dir()
+        .join(format!("monitor-profiles-{}", std::process::id()))
+        .join("profiles.json");
+
+    let profiles = DisplayProfiles::load(&path).unwrap();
+
+    assert!(profiles.get("arrangement", "main").is_none());
+
+    let frame = Rect::new(10.0, 20.0, 300.0, 400.0);
+
+    profiles.insert("arrangement", "main", frame).unwrap();
+
+    let reloaded = DisplayProfiles::load(&path).unwrap();
+
+    assert_eq!(reloaded.get("arrangement", "main"), Some(frame));
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
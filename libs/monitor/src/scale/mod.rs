@@ -0,0 +1,185 @@
+//! Conversions between physical and logical units that pick the scale factor of the
+//! monitor a point lies on, rather than whichever monitor happens to be at hand.
+
+use tauri::{LogicalPosition, PhysicalPosition};
+
+use crate::{desktop, Monitor, Rect};
+
+mod tests;
+
+/// How the windowing system lays monitors out.
+#[derive(Clone, Copy)]
+enum Layout {
+    /// Monitors are placed in logical points, and a physical position is a point times
+    /// the scale factor of the screen it is on, as on macOS. Screens with different
+    /// scale factors can then overlap in physical units.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    Points,
+    /// Monitors are placed in physical pixels, and each has a logical frame of its own,
+    /// its position and size divided by its scale factor. Those frames can leave gaps
+    /// or overlap.
+    Pixels,
+}
+
+impl Layout {
+    #[cfg(target_os = "macos")]
+    const NATIVE: Layout = Layout::Points;
+
+    #[cfg(not(target_os = "macos"))]
+    const NATIVE: Layout = Layout::Pixels;
+
+    fn logical_frame(self, monitor: &Monitor, monitors: &[Monitor]) -> Rect {
+        match self {
+            Layout::Points => desktop::from_appkit(
+                monitor.position,
+                monitor.size,
+                monitor.scale_factor,
+                monitors,
+            ),
+            Layout::Pixels => {
+                let position = monitor.logical_position();
+
+                let size = monitor.logical_size();
+
+                Rect::new(position.x, position.y, size.width, size.height)
+            }
+        }
+    }
+
+    fn physical_frame(self, monitor: &Monitor, monitors: &[Monitor]) -> Rect {
+        match self {
+            Layout::Points => {
+                let frame = self.logical_frame(monitor, monitors);
+
+                Rect::new(
+                    frame.x * monitor.scale_factor,
+                    frame.y * monitor.scale_factor,
+                    frame.width * monitor.scale_factor,
+                    frame.height * monitor.scale_factor,
+                )
+            }
+            Layout::Pixels => Rect::new(
+                monitor.position.x,
+                monitor.position.y,
+                monitor.size.width,
+                monitor.size.height,
+            ),
+        }
+    }
+
+    fn monitor_from_physical(
+        self,
+        monitors: &[Monitor],
+        position: PhysicalPosition<f64>,
+    ) -> Option<&Monitor> {
+        monitors.iter().find(|monitor| {
+            self.physical_frame(monitor, monitors)
+                .contains(position.x, position.y)
+        })
+    }
+
+    fn monitor_from_logical(
+        self,
+        monitors: &[Monitor],
+        position: LogicalPosition<f64>,
+    ) -> Option<&Monitor> {
+        monitors.iter().find(|monitor| {
+            self.logical_frame(monitor, monitors)
+                .contains(position.x, position.y)
+        })
+    }
+
+    fn physical_to_logical(
+        self,
+        monitors: &[Monitor],
+        position: PhysicalPosition<f64>,
+    ) -> Option<LogicalPosition<f64>> {
+        let monitor = self.monitor_from_physical(monitors, position)?;
+
+        let physical = self.physical_frame(monitor, monitors);
+
+        let logical = self.logical_frame(monitor, monitors);
+
+        Some(LogicalPosition::new(
+            logical.x + (position.x - physical.x) / monitor.scale_factor,
+            logical.y + (position.y - physical.y) / monitor.scale_factor,
+        ))
+    }
+
+    fn logical_to_physical(
+        self,
+        monitors: &[Monitor],
+        position: LogicalPosition<f64>,
+    ) -> Option<PhysicalPosition<f64>> {
+        let monitor = self.monitor_from_logical(monitors, position)?;
+
+        let physical = self.physical_frame(monitor, monitors);
+
+        let logical = self.logical_frame(monitor, monitors);
+
+        Some(PhysicalPosition::new(
+            physical.x + (position.x - logical.x) * monitor.scale_factor,
+            physical.y + (position.y - logical.y) * monitor.scale_factor,
+        ))
+    }
+}
+
+/// Finds the monitor containing `position`, in physical pixels with a top-left origin.
+/// On macOS, that is a point times the scale factor of the screen it is on, as
+/// `get_cursor_position()` reports it; where screens with different scale factors
+/// overlap in those units, the first one in `monitors` wins.
+pub fn monitor_from_physical(
+    monitors: &[Monitor],
+    position: PhysicalPosition<f64>,
+) -> Option<&Monitor> {
+    Layout::NATIVE.monitor_from_physical(monitors, position)
+}
+
+/// Finds the monitor containing `position`, in logical units. On macOS, that is the
+/// top-left points windows are positioned in; elsewhere, each monitor's logical frame
+/// is its position and size divided by its own scale factor.
+pub fn monitor_from_logical(
+    monitors: &[Monitor],
+    position: LogicalPosition<f64>,
+) -> Option<&Monitor> {
+    Layout::NATIVE.monitor_from_logical(monitors, position)
+}
+
+/// Converts `position` to logical units, keeping its offset on the monitor it lies on.
+/// Returns `None` when it is on no monitor.
+pub fn physical_to_logical(
+    monitors: &[Monitor],
+    position: PhysicalPosition<f64>,
+) -> Option<LogicalPosition<f64>> {
+    Layout::NATIVE.physical_to_logical(monitors, position)
+}
+
+/// Converts `position` to physical units, keeping its offset on the monitor it lies on.
+/// Returns `None` when it is on no monitor.
+pub fn logical_to_physical(
+    monitors: &[Monitor],
+    position: LogicalPosition<f64>,
+) -> Option<PhysicalPosition<f64>> {
+    Layout::NATIVE.logical_to_physical(monitors, position)
+}
+
+/// Converts `rect`, a window frame in physical pixels on monitor `from`, to the frame
+/// it should have on monitor `to`: the same logical size, at the same logical offset
+/// from the monitor's top-left corner, kept within `to`.
+pub fn move_rect_to_monitor(rect: &Rect, from: &Monitor, to: &Monitor) -> Rect {
+    let ratio = to.scale_factor / from.scale_factor;
+
+    let moved = Rect::new(
+        to.position.x + (rect.x - from.position.x) * ratio,
+        to.position.y + (rect.y - from.position.y) * ratio,
+        rect.width * ratio,
+        rect.height * ratio,
+    );
+
+    moved.clamp_to(&Rect::new(
+        to.position.x,
+        to.position.y,
+        to.size.width,
+        to.size.height,
+    ))
+}
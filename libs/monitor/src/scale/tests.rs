@@ -0,0 +1,224 @@
+#![cfg(test)]
+
+use tauri::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+
+use crate::{fixtures::monitor, Monitor, Rect, VisibleArea};
+
+use super::{
+    logical_to_physical, monitor_from_logical, monitor_from_physical, move_rect_to_monitor,
+    physical_to_logical, Layout,
+};
+
+/// A 2x laptop, a 1x monitor to its right and a 1.5x 4K monitor to its left.
+fn mixed_dpi() -> Vec<Monitor> {
+    vec![
+        monitor(1, None, Rect::new(0.0, 0.0, 2880.0, 1800.0), 2.0),
+        monitor(2, None, Rect::new(2880.0, 0.0, 2560.0, 1440.0), 1.0),
+        monitor(3, None, Rect::new(-3840.0, 0.0, 3840.0, 2160.0), 1.5),
+    ]
+}
+
+#[test]
+fn it_gets_logical_geometry_with_the_monitors_own_scale() {
+    let mut monitors = mixed_dpi();
+
+    // A 50 point menubar.
+    monitors[0].visible_area = VisibleArea {
+        size: PhysicalSize::new(2880.0, 1700.0),
+        position: PhysicalPosition::new(0.0, 100.0),
+        scale_factor: 2.0,
+    };
+
+    assert_eq!(monitors[0].logical_size(), LogicalSize::new(1440.0, 900.0));
+    assert_eq!(monitors[2].logical_size(), LogicalSize::new(2560.0, 1440.0));
+    assert_eq!(
+        monitors[2].logical_position(),
+        LogicalPosition::new(-2560.0, 0.0)
+    );
+
+    let visible_area = monitors[0].visible_area();
+
+    assert_eq!(visible_area.logical_size(), LogicalSize::new(1440.0, 850.0));
+    assert_eq!(
+        visible_area.logical_position(),
+        LogicalPosition::new(0.0, 50.0)
+    );
+}
+
+#[test]
+fn it_reads_visible_areas_saved_without_a_scale_factor() {
+    let visible_area: VisibleArea = serde_json::from_str(
+        r#"{ "size": { "width": 1440, "height": 850 }, "position": { "x": 0, "y": 50 } }"#,
+    )
+    .unwrap();
+
+    assert_eq!(visible_area.scale_factor(), 1.0);
+    assert_eq!(visible_area.logical_size(), LogicalSize::new(1440.0, 850.0));
+}
+
+#[test]
+fn it_finds_the_monitor_owning_a_point() {
+    let monitors = mixed_dpi();
+
+    let owner =
+        |x, y| monitor_from_physical(&monitors, PhysicalPosition::new(x, y)).map(Monitor::id);
+
+    assert_eq!(owner(2879.0, 100.0), Some(1));
+    assert_eq!(owner(2880.0, 100.0), Some(2));
+    assert_eq!(owner(-1.0, 100.0), Some(3));
+    assert_eq!(owner(3000.0, 1500.0), None);
+
+    let logical_owner =
+        |x, y| monitor_from_logical(&monitors, LogicalPosition::new(x, y)).map(Monitor::id);
+
+    assert_eq!(logical_owner(1000.0, 100.0), Some(1));
+    assert_eq!(logical_owner(-100.0, 100.0), Some(3));
+}
+
+#[test]
+fn it_converts_points_with_their_owning_monitors_scale() {
+    let monitors = mixed_dpi();
+
+    assert_eq!(
+        physical_to_logical(&monitors, PhysicalPosition::new(2000.0, 1000.0)),
+        Some(LogicalPosition::new(1000.0, 500.0))
+    );
+
+    assert_eq!(
+        physical_to_logical(&monitors, PhysicalPosition::new(3000.0, 1000.0)),
+        Some(LogicalPosition::new(3000.0, 1000.0))
+    );
+
+    assert_eq!(
+        physical_to_logical(&monitors, PhysicalPosition::new(-1500.0, 300.0)),
+        Some(LogicalPosition::new(-1000.0, 200.0))
+    );
+
+    assert_eq!(
+        logical_to_physical(&monitors, LogicalPosition::new(-1000.0, 200.0)),
+        Some(PhysicalPosition::new(-1500.0, 300.0))
+    );
+
+    assert_eq!(
+        physical_to_logical(&monitors, PhysicalPosition::new(3000.0, 1500.0)),
+        None
+    );
+}
+
+#[test]
+fn it_leaves_points_between_logical_frames_on_no_monitor() {
+    let monitors = mixed_dpi();
+
+    // The laptop ends at 1440 logical points and the external monitor starts at 2880.
+    assert!(monitor_from_logical(&monitors, LogicalPosition::new(2000.0, 100.0)).is_none());
+
+    assert_eq!(
+        logical_to_physical(&monitors, LogicalPosition::new(2000.0, 100.0)),
+        None
+    );
+
+    // Every physical point on either side of the seam still converts, and back.
+    for x in [2879.0, 2880.0] {
+        let position = PhysicalPosition::new(x, 100.0);
+
+        let logical = physical_to_logical(&monitors, position).unwrap();
+
+        assert_eq!(logical_to_physical(&monitors, logical), Some(position));
+    }
+}
+
+/// As AppKit reports them: a 2x laptop and a 1x monitor to its right, placed in points
+/// from the bottom-left corner, with the positions scaled by each screen's own factor.
+fn appkit_mixed_dpi() -> Vec<Monitor> {
+    vec![
+        monitor(1, None, Rect::new(0.0, 0.0, 2880.0, 1800.0), 2.0),
+        monitor(2, None, Rect::new(1440.0, 0.0, 2560.0, 1440.0), 1.0),
+    ]
+}
+
+#[test]
+fn it_converts_points_in_appkit_layouts() {
+    let monitors = appkit_mixed_dpi();
+
+    let logical_owner = |x, y| {
+        Layout::Points
+            .monitor_from_logical(&monitors, LogicalPosition::new(x, y))
+            .map(Monitor::id)
+    };
+
+    // The external monitor's bottom edge lines up with the laptop's, so it reaches 540
+    // points above the laptop's top.
+    assert_eq!(logical_owner(1000.0, 100.0), Some(1));
+    assert_eq!(logical_owner(2000.0, 100.0), Some(2));
+    assert_eq!(logical_owner(2000.0, -300.0), Some(2));
+    assert_eq!(logical_owner(1000.0, -300.0), None);
+
+    assert_eq!(
+        Layout::Points.logical_to_physical(&monitors, LogicalPosition::new(1000.0, 100.0)),
+        Some(PhysicalPosition::new(2000.0, 200.0))
+    );
+
+    assert_eq!(
+        Layout::Points.logical_to_physical(&monitors, LogicalPosition::new(2000.0, -300.0)),
+        Some(PhysicalPosition::new(2000.0, -300.0))
+    );
+
+    assert_eq!(
+        Layout::Points.physical_to_logical(&monitors, PhysicalPosition::new(3000.0, -300.0)),
+        Some(LogicalPosition::new(3000.0, -300.0))
+    );
+
+    assert_eq!(
+        Layout::Points.physical_to_logical(&monitors, PhysicalPosition::new(1000.0, 200.0)),
+        Some(LogicalPosition::new(500.0, 100.0))
+    );
+
+    // (2000, 200) is both the laptop's (1000, 100) and the external monitor's own
+    // (2000, 200); the first monitor listed wins.
+    assert_eq!(
+        Layout::Points
+            .monitor_from_physical(&monitors, PhysicalPosition::new(2000.0, 200.0))
+            .map(Monitor::id),
+        Some(1)
+    );
+}
+
+#[test]
+fn it_moves_rects_between_monitors_with_different_scales() {
+    let monitors = mixed_dpi();
+
+    // 800x600 logical, 100x100 logical from the laptop's corner.
+    let rect = Rect::new(200.0, 200.0, 1600.0, 1200.0);
+
+    assert_eq!(
+        move_rect_to_monitor(&rect, &monitors[0], &monitors[1]),
+        Rect::new(2980.0, 100.0, 800.0, 600.0)
+    );
+
+    assert_eq!(
+        move_rect_to_monitor(&rect, &monitors[0], &monitors[2]),
+        Rect::new(-3690.0, 150.0, 1200.0, 900.0)
+    );
+
+    let back = move_rect_to_monitor(
+        &move_rect_to_monitor(&rect, &monitors[0], &monitors[2]),
+        &monitors[2],
+        &monitors[0],
+    );
+
+    assert_eq!(back, rect);
+}
+
+#[test]
+fn it_keeps_moved_rects_within_the_target_monitor() {
+    let monitors = mixed_dpi();
+
+    // Near the external monitor's bottom-right corner, which lies beyond the laptop's
+    // once doubled.
+    let rect = Rect::new(4880.0, 1200.0, 500.0, 200.0);
+
+    assert_eq!(
+        move_rect_to_monitor(&rect, &monitors[1], &monitors[0]),
+        Rect::new(1880.0, 1400.0, 1000.0, 400.0)
+    );
+}
@@ -0,0 +1,91 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, TryRecvError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+mod tests;
+
+/// Receives the events a thread finds by polling every so often. Polling stops when the
+/// stream is stopped or dropped.
+pub struct PollingStream<T> {
+    receiver: Receiver<T>,
+    running: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> PollingStream<T> {
+    /// Polls on a new thread: `setup` runs there first and returns the poll function, or
+    /// `None` when there is nothing to poll, which ends the stream. The poll function is
+    /// then called right away and every `interval` after, and the events it returns are
+    /// sent in order.
+    pub fn spawn<S, P, I>(interval: Duration, setup: S) -> Self
+    where
+        S: FnOnce() -> Option<P> + Send + 'static,
+        P: FnMut() -> I,
+        I: IntoIterator<Item = T>,
+    {
+        let (sender, receiver) = mpsc::channel();
+
+        let running = Arc::new(AtomicBool::new(true));
+
+        let is_running = running.clone();
+
+        thread::spawn(move || {
+            let mut poll = match setup() {
+                Some(poll) => poll,
+                None => return,
+            };
+
+            while is_running.load(Ordering::Relaxed) {
+                for event in poll() {
+                    if sender.send(event).is_err() {
+                        return;
+                    }
+                }
+
+                thread::sleep(interval);
+            }
+        });
+
+        Self { receiver, running }
+    }
+}
+
+impl<T> PollingStream<T> {
+    /// Blocks until the next event, or returns `None` once polling has ended.
+    pub fn recv(&self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+
+    /// Blocks until the next event or `timeout` elapses.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)
+    }
+
+    /// Returns the next pending event without blocking.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<T> Iterator for PollingStream<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.recv()
+    }
+}
+
+impl<T> Drop for PollingStream<T> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,70 @@
+#![cfg(test)]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use super::PollingStream;
+
+const INTERVAL: Duration = Duration::from_millis(5);
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+#[test]
+fn it_sends_every_polled_event_in_order() {
+    let mut count = 0;
+
+    let stream = PollingStream::spawn(INTERVAL, move || {
+        Some(move || {
+            count += 1;
+
+            vec![count * 10, count * 10 + 1]
+        })
+    });
+
+    let events = (0..4)
+        .map(|_| stream.recv_timeout(TIMEOUT).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(events, [10, 11, 20, 21]);
+}
+
+#[test]
+fn it_ends_when_there_is_nothing_to_poll() {
+    let mut stream = PollingStream::spawn(INTERVAL, || None::<fn() -> Option<u32>>);
+
+    assert_eq!(stream.next(), None);
+}
+
+#[test]
+fn it_stops_polling_when_dropped() {
+    let polls = Arc::new(AtomicUsize::new(0));
+
+    let counter = polls.clone();
+
+    let stream = PollingStream::spawn(INTERVAL, move || {
+        Some(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+
+            None::<()>
+        })
+    });
+
+    thread::sleep(INTERVAL * 4);
+
+    drop(stream);
+
+    thread::sleep(INTERVAL * 4);
+
+    let stopped_at = polls.load(Ordering::Relaxed);
+
+    thread::sleep(INTERVAL * 4);
+
+    assert!(stopped_at > 0);
+    assert_eq!(polls.load(Ordering::Relaxed), stopped_at);
+}
@@ -0,0 +1,236 @@
+use std::{
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use tauri::{Runtime, WebviewWindow, WindowEvent};
+
+use crate::{
+    desktop,
+    displays::on_displays_changed,
+    get_monitors,
+    placement::{Placement, PlacementError, PlacementMonitor},
+    profile::{clamp_to_nearest, fingerprint, DisplayProfiles, ProfileError, Restored},
+    Monitor, Rect,
+};
+
+/// How long a tracked window has to stay still before its frame is saved, so a drag
+/// enumerates the monitors and writes the profiles once, when it ends.
+const PROFILE_SETTLE: Duration = Duration::from_millis(500);
+
+/// What a tracked window reports to the thread saving its profile.
+enum ProfileEvent {
+    /// The window moved, resized or changed scale factor, or the displays were
+    /// reconfigured.
+    Changed,
+    Destroyed,
+}
+
+/// Restores `window` when the display arrangement differs from `last_fingerprint`, and
+/// otherwise saves its frame. Runs on the main thread, where AppKit reads the screens.
+fn settle<R: Runtime>(
+    window: &WebviewWindow<R>,
+    profiles: &DisplayProfiles,
+    last_fingerprint: &Mutex<Option<String>>,
+) {
+    let current = fingerprint(&get_monitors());
+
+    let mut last = last_fingerprint.lock().unwrap();
+
+    // The OS moves windows around when displays are attached or detached, so a change
+    // under a new arrangement is a cue to restore, not to save.
+    if last.as_deref() != Some(current.as_str()) {
+        *last = Some(current);
+
+        let _ = window.restore_display_profile(profiles);
+    } else {
+        let _ = window.remember_display_profile(profiles);
+    }
+}
+
+pub trait WebviewWindowExt {
+    /// Saves the window's frame for the current display arrangement.
+    fn remember_display_profile(&self, profiles: &DisplayProfiles) -> Result<(), ProfileError>;
+
+    /// Moves the window to the frame saved for the current display arrangement,
+    /// or onto the nearest monitor if none was saved.
+    fn restore_display_profile(&self, profiles: &DisplayProfiles)
+        -> Result<Restored, ProfileError>;
+
+    /// Restores the window now, then keeps its frame saved as it moves and restores it
+    /// whenever the display arrangement changes, even if the window itself is not moved.
+    /// Changes are handled once the window and the displays have settled, and when the
+    /// window is about to close.
+    fn track_display_profile(&self, profiles: Arc<DisplayProfiles>);
+
+    /// Moves the window according to `placement` within the visible area of `monitor`.
+    /// The window keeps its logical size when `monitor` has a different scale factor.
+    fn place(&self, placement: Placement, monitor: PlacementMonitor) -> Result<(), PlacementError>;
+}
+
+impl<R: Runtime> WebviewWindowExt for WebviewWindow<R> {
+    fn remember_display_profile(&self, profiles: &DisplayProfiles) -> Result<(), ProfileError> {
+        let monitors = get_monitors();
+
+        if monitors.is_empty() {
+            return Err(ProfileError::NoMonitors);
+        }
+
+        let frame = desktop::window_frame(self)?;
+
+        profiles.insert(&fingerprint(&monitors), self.label(), frame)
+    }
+
+    fn restore_display_profile(
+        &self,
+        profiles: &DisplayProfiles,
+    ) -> Result<Restored, ProfileError> {
+        let monitors = get_monitors();
+
+        if monitors.is_empty() {
+            return Err(ProfileError::NoMonitors);
+        }
+
+        if let Some(frame) = profiles.get(&fingerprint(&monitors), self.label()) {
+            desktop::set_window_frame(self, &frame)?;
+
+            return Ok(Restored::Profile);
+        }
+
+        let frame = desktop::window_frame(self)?;
+
+        let visible_frames = monitors
+            .iter()
+            .map(|monitor| desktop::visible_frame(monitor, &monitors))
+            .collect::<Vec<Rect>>();
+
+        let clamped = clamp_to_nearest(&frame, &visible_frames);
+
+        if clamped == frame {
+            return Ok(Restored::Unchanged);
+        }
+
+        desktop::set_window_frame(self, &clamped)?;
+
+        Ok(Restored::Clamped)
+    }
+
+    fn track_display_profile(&self, profiles: Arc<DisplayProfiles>) {
+        let last_fingerprint = Arc::new(Mutex::new(None));
+
+        let settle_on_main_thread = {
+            let window = self.clone();
+
+            let last_fingerprint = last_fingerprint.clone();
+
+            let profiles = profiles.clone();
+
+            move || {
+                let (win, profiles, last_fingerprint) =
+                    (window.clone(), profiles.clone(), last_fingerprint.clone());
+
+                window.run_on_main_thread(move || settle(&win, &profiles, &last_fingerprint))
+            }
+        };
+
+        // No arrangement is known yet, so this restores the window.
+        let _ = settle_on_main_thread();
+
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            while let Ok(ProfileEvent::Changed) = receiver.recv() {
+                // Waits for the window or the displays to settle.
+                loop {
+                    match receiver.recv_timeout(PROFILE_SETTLE) {
+                        Ok(ProfileEvent::Changed) => {}
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Ok(ProfileEvent::Destroyed) | Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+
+                if settle_on_main_thread().is_err() {
+                    return;
+                }
+            }
+        });
+
+        // Displays may be re-docked without the OS moving the window.
+        let displays_sender = sender.clone();
+
+        on_displays_changed(move || displays_sender.send(ProfileEvent::Changed).is_ok());
+
+        let window = self.clone();
+
+        self.on_window_event(move |event| match event {
+            WindowEvent::Moved(_)
+            | WindowEvent::Resized(_)
+            | WindowEvent::ScaleFactorChanged { .. } => {
+                let _ = sender.send(ProfileEvent::Changed);
+            }
+            // Window events are handled on the main thread, so pending changes are saved
+            // while the window is still there.
+            WindowEvent::CloseRequested { .. } => settle(&window, &profiles, &last_fingerprint),
+            WindowEvent::Destroyed => {
+                let _ = sender.send(ProfileEvent::Destroyed);
+            }
+            _ => {}
+        });
+    }
+
+    fn place(&self, placement: Placement, monitor: PlacementMonitor) -> Result<(), PlacementError> {
+        let monitors = get_monitors();
+
+        let frame = desktop::window_frame(self)?;
+
+        let overlap = |monitor: &Monitor| {
+            frame
+                .intersection(&desktop::monitor_frame(monitor, &monitors))
+                .map(|overlap| overlap.area())
+                .unwrap_or_default()
+        };
+
+        let target = match monitor {
+            PlacementMonitor::Current => monitors
+                .iter()
+                .max_by(|a, b| overlap(a).total_cmp(&overlap(b))),
+            PlacementMonitor::WithCursor => monitors.iter().find(|monitor| monitor.has_cursor),
+            PlacementMonitor::Primary => monitors.iter().find(|monitor| monitor.is_primary),
+            PlacementMonitor::Id(id) => monitors.iter().find(|monitor| monitor.id == id),
+        }
+        .ok_or(PlacementError::MonitorNotFound)?;
+
+        // Window frames are in logical points on macOS, where margins need no scaling.
+        // Elsewhere they are physical pixels, and the window keeps its logical size on
+        // a monitor with a different scale factor.
+        #[cfg(target_os = "macos")]
+        let (scale_factor, width, height) = (1.0, frame.width, frame.height);
+
+        #[cfg(not(target_os = "macos"))]
+        let (scale_factor, width, height) = {
+            let ratio = target.scale_factor / self.scale_factor()?;
+
+            (
+                target.scale_factor,
+                frame.width * ratio,
+                frame.height * ratio,
+            )
+        };
+
+        let placed = placement.frame(
+            &desktop::visible_frame(target, &monitors),
+            width,
+            height,
+            scale_factor,
+            desktop::cursor_position(&monitors),
+        );
+
+        desktop::set_window_frame(self, &placed)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+
+use crate::shape::{ArrowEdge, Point};
+
+mod tests;
+
+/// The scale a popover grows from as it appears.
+const SCALE_FROM: f64 = 0.9;
+
+/// How far a popover slides in from its anchor as it appears.
+const SLIDE_DISTANCE: f64 = 12.0;
+
+/// How a popover appears and disappears. Each style also fades the popover.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum AnimationStyle {
+    /// Shows and hides at once.
+    None,
+    Fade,
+    /// Grows from the tip of the arrow.
+    #[default]
+    Scale,
+    /// Slides in from the anchor, along the direction the arrow points.
+    Slide,
+}
+
+/// How an animation's progress follows its time, as in CSS.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    #[default]
+    EaseOut,
+    EaseInOut,
+    /// A cubic Bézier curve from `(0, 0)` to `(1, 1)` through the control points
+    /// `(x1, y1)` and `(x2, y2)`, written `[x1, y1, x2, y2]`. The x coordinates are kept
+    /// within 0 and 1.
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl Easing {
+    /// The progress of an animation `time` of the way through, both from 0 to 1.
+    pub fn apply(&self, time: f64) -> f64 {
+        let (x1, y1, x2, y2) = match *self {
+            Self::Linear => return time.clamp(0.0, 1.0),
+            Self::EaseIn => (0.42, 0.0, 1.0, 1.0),
+            Self::EaseOut => (0.0, 0.0, 0.58, 1.0),
+            Self::EaseInOut => (0.42, 0.0, 0.58, 1.0),
+            Self::CubicBezier(x1, y1, x2, y2) => (x1, y1, x2, y2),
+        };
+
+        if time <= 0.0 {
+            return 0.0;
+        }
+
+        if time >= 1.0 {
+            return 1.0;
+        }
+
+        let sample = |from: f64, to: f64, t: f64| {
+            let u = 1.0 - t;
+
+            3.0 * u * u * t * from + 3.0 * u * t * t * to + t * t * t
+        };
+
+        let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+
+        // With its x coordinates within 0 and 1, the curve's x only grows, so the point
+        // at `time` can be found by bisection.
+        let (mut low, mut high) = (0.0, 1.0);
+
+        let mut t = time;
+
+        for _ in 0..48 {
+            let x = sample(x1, x2, t);
+
+            if (x - time).abs() < 1e-7 {
+                break;
+            }
+
+            if x < time {
+                low = t;
+            } else {
+                high = t;
+            }
+
+            t = (low + high) / 2.0;
+        }
+
+        sample(y1, y2, t)
+    }
+}
+
+/// How a popover animates when shown with `Popover::show_animated` and hidden with
+/// `Popover::hide_animated`. Durations are in seconds. Missing fields take their default
+/// when deserialized.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PopoverAnimation {
+    pub style: AnimationStyle,
+    pub show_duration: f64,
+    pub hide_duration: f64,
+    pub easing: Easing,
+    /// The style used instead of `Scale` and `Slide` when the user has asked the OS to
+    /// reduce motion.
+    pub reduced_motion_style: AnimationStyle,
+}
+
+impl Default for PopoverAnimation {
+    fn default() -> Self {
+        Self {
+            style: AnimationStyle::Scale,
+            show_duration: 0.2,
+            hide_duration: 0.15,
+            easing: Easing::EaseOut,
+            reduced_motion_style: AnimationStyle::Fade,
+        }
+    }
+}
+
+impl PopoverAnimation {
+    /// The style to animate with, given whether the user has asked the OS to reduce
+    /// motion. Only moving styles are replaced, and never by a moving one.
+    pub fn style(&self, reduce_motion: bool) -> AnimationStyle {
+        let moves =
+            |style: AnimationStyle| matches!(style, AnimationStyle::Scale | AnimationStyle::Slide);
+
+        if !reduce_motion || !moves(self.style) {
+            return self.style;
+        }
+
+        if moves(self.reduced_motion_style) {
+            AnimationStyle::Fade
+        } else {
+            self.reduced_motion_style
+        }
+    }
+
+    /// How long showing, or else hiding, takes.
+    pub fn duration(&self, showing: bool) -> f64 {
+        if showing {
+            self.show_duration
+        } else {
+            self.hide_duration
+        }
+    }
+}
+
+/// How a popover is drawn at one point of an animation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct AnimationFrame {
+    pub opacity: f64,
+    /// The scale of the popover around the tip of its arrow.
+    pub scale: f64,
+    /// How far the window is moved from where it was placed, in the coordinate space
+    /// windows are positioned in.
+    pub offset: Point,
+}
+
+impl AnimationFrame {
+    /// A popover fully shown.
+    pub const SHOWN: Self = Self {
+        opacity: 1.0,
+        scale: 1.0,
+        offset: Point { x: 0.0, y: 0.0 },
+    };
+}
+
+/// The frame of a popover `progress` of the way from hidden, at 0, to shown, at 1, with
+/// its arrow on `arrow_edge`.
+pub fn frame(style: AnimationStyle, progress: f64, arrow_edge: ArrowEdge) -> AnimationFrame {
+    let progress = progress.clamp(0.0, 1.0);
+
+    let remaining = 1.0 - progress;
+
+    match style {
+        AnimationStyle::None => AnimationFrame::SHOWN,
+        AnimationStyle::Fade => AnimationFrame {
+            opacity: progress,
+            ..AnimationFrame::SHOWN
+        },
+        AnimationStyle::Scale => AnimationFrame {
+            opacity: progress,
+            scale: SCALE_FROM + (1.0 - SCALE_FROM) * progress,
+            ..AnimationFrame::SHOWN
+        },
+        AnimationStyle::Slide => {
+            let distance = SLIDE_DISTANCE * remaining;
+
+            // Starts on the anchor's side, which the arrow points to.
+            let (x, y) = match arrow_edge {
+                ArrowEdge::Bottom => (0.0, distance),
+                ArrowEdge::Left => (-distance, 0.0),
+                ArrowEdge::Right => (distance, 0.0),
+                _ => (0.0, -distance),
+            };
+
+            AnimationFrame {
+                opacity: progress,
+                offset: Point::new(x, y),
+                ..AnimationFrame::SHOWN
+            }
+        }
+    }
+}
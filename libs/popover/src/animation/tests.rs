@@ -0,0 +1,154 @@
+#![cfg(test)]
+
+use crate::shape::{ArrowEdge, Point};
+
+use super::{frame, AnimationFrame, AnimationStyle, Easing, PopoverAnimation};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "{} is not close to {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn it_eases_like_css() {
+    for easing in [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ] {
+        assert_eq!(easing.apply(0.0), 0.0);
+
+        assert_eq!(easing.apply(1.0), 1.0);
+    }
+
+    assert_close(Easing::Linear.apply(0.3), 0.3);
+
+    assert_close(Easing::EaseIn.apply(0.5), 0.315);
+
+    assert_close(Easing::EaseOut.apply(0.5), 0.685);
+
+    assert_close(Easing::EaseInOut.apply(0.5), 0.5);
+
+    assert_close(Easing::CubicBezier(0.0, 0.0, 1.0, 1.0).apply(0.25), 0.25);
+}
+
+#[test]
+fn it_eases_forward() {
+    let easing = Easing::CubicBezier(0.2, 0.9, 0.1, 1.0);
+
+    let samples: Vec<f64> = (0..=20)
+        .map(|step| easing.apply(step as f64 / 20.0))
+        .collect();
+
+    assert!(samples.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn it_grows_from_the_arrow_tip() {
+    let start = frame(AnimationStyle::Scale, 0.0, ArrowEdge::Top);
+
+    assert_eq!(start.opacity, 0.0);
+
+    assert_close(start.scale, 0.9);
+
+    assert_eq!(
+        frame(AnimationStyle::Scale, 1.0, ArrowEdge::Top),
+        AnimationFrame::SHOWN
+    );
+}
+
+#[test]
+fn it_slides_in_from_the_anchor() {
+    let cases = [
+        (ArrowEdge::Top, Point::new(0.0, -6.0)),
+        (ArrowEdge::Bottom, Point::new(0.0, 6.0)),
+        (ArrowEdge::Left, Point::new(-6.0, 0.0)),
+        (ArrowEdge::Right, Point::new(6.0, 0.0)),
+    ];
+
+    for (edge, expected) in cases {
+        let halfway = frame(AnimationStyle::Slide, 0.5, edge);
+
+        assert_eq!(halfway.offset, expected, "{:?}", edge);
+
+        assert_eq!(halfway.opacity, 0.5);
+
+        assert_eq!(halfway.scale, 1.0);
+    }
+}
+
+#[test]
+fn it_fades_or_shows_at_once_without_moving() {
+    assert_eq!(
+        frame(AnimationStyle::Fade, 0.25, ArrowEdge::Left),
+        AnimationFrame {
+            opacity: 0.25,
+            ..AnimationFrame::SHOWN
+        }
+    );
+
+    assert_eq!(
+        frame(AnimationStyle::None, 0.0, ArrowEdge::Top),
+        AnimationFrame::SHOWN
+    );
+}
+
+#[test]
+fn it_falls_back_when_motion_is_reduced() {
+    let animation = PopoverAnimation::default();
+
+    assert_eq!(animation.style(false), AnimationStyle::Scale);
+
+    assert_eq!(animation.style(true), AnimationStyle::Fade);
+
+    let animation = PopoverAnimation {
+        style: AnimationStyle::Slide,
+        reduced_motion_style: AnimationStyle::None,
+        ..PopoverAnimation::default()
+    };
+
+    assert_eq!(animation.style(true), AnimationStyle::None);
+
+    // A moving fallback would defeat the point.
+    let animation = PopoverAnimation {
+        reduced_motion_style: AnimationStyle::Slide,
+        ..PopoverAnimation::default()
+    };
+
+    assert_eq!(animation.style(true), AnimationStyle::Fade);
+
+    let animation = PopoverAnimation {
+        style: AnimationStyle::Fade,
+        reduced_motion_style: AnimationStyle::None,
+        ..PopoverAnimation::default()
+    };
+
+    assert_eq!(animation.style(true), AnimationStyle::Fade);
+}
+
+#[test]
+fn it_reads_animations_from_json() {
+    let animation: PopoverAnimation = serde_json::from_str(
+        r#"{
+            "style": "slide",
+            "showDuration": 0.3,
+            "easing": { "cubicBezier": [0.2, 0.9, 0.1, 1] }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        animation,
+        PopoverAnimation {
+            style: AnimationStyle::Slide,
+            show_duration: 0.3,
+            easing: Easing::CubicBezier(0.2, 0.9, 0.1, 1.0),
+            ..PopoverAnimation::default()
+        }
+    );
+}
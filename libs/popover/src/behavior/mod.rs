@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+mod tests;
+
+/// The event emitted, with a `DismissedEvent` payload, when a popover's behavior hides its
+/// window.
+pub const DISMISSED_EVENT: &str = "popover-dismissed";
+
+/// When a popover closes itself, as `NSPopoverBehavior` does.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum PopoverBehavior {
+    /// The app hides the popover itself.
+    #[default]
+    ApplicationDefined,
+    /// Closes on a click outside the popover, on Escape, or when the popover loses focus.
+    /// On Linux, clicks outside the app are seen through a pointer grab, which swallows
+    /// the click, and which Wayland only grants to popup windows.
+    Transient,
+    /// Like `Transient`, but stays open while the user works in another of the app's
+    /// windows.
+    Semitransient,
+}
+
+/// Why a popover closed itself.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DismissReason {
+    ClickOutside,
+    FocusLost,
+    Escape,
+}
+
+/// Something the user did while a popover was shown. `within_app` is set when the click
+/// landed on, or the focus moved to, another of the app's windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interaction {
+    ClickOutside { within_app: bool },
+    FocusLost { within_app: bool },
+    Escape,
+}
+
+impl PopoverBehavior {
+    /// Why a popover with this behavior closes on `interaction`, or `None` if it stays open.
+    pub fn dismiss_reason(&self, interaction: Interaction) -> Option<DismissReason> {
+        let (reason, within_app) = match interaction {
+            Interaction::ClickOutside { within_app } => (DismissReason::ClickOutside, within_app),
+            Interaction::FocusLost { within_app } => (DismissReason::FocusLost, within_app),
+            Interaction::Escape => (DismissReason::Escape, false),
+        };
+
+        match self {
+            Self::ApplicationDefined => None,
+            Self::Transient => Some(reason),
+            Self::Semitransient if within_app => None,
+            Self::Semitransient => Some(reason),
+        }
+    }
+}
+
+/// The payload of `DISMISSED_EVENT`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DismissedEvent {
+    /// The label of the popover's window.
+    pub label: String,
+    pub reason: DismissReason,
+}
@@ -0,0 +1,86 @@
+#![cfg(test)]
+
+use super::{DismissReason, DismissedEvent, Interaction, PopoverBehavior};
+
+#[test]
+fn it_leaves_application_defined_popovers_open() {
+    for interaction in [
+        Interaction::ClickOutside { within_app: false },
+        Interaction::FocusLost { within_app: false },
+        Interaction::Escape,
+    ] {
+        assert_eq!(
+            PopoverBehavior::ApplicationDefined.dismiss_reason(interaction),
+            None
+        );
+    }
+}
+
+#[test]
+fn it_closes_transient_popovers_on_any_interaction() {
+    let cases = [
+        (
+            Interaction::ClickOutside { within_app: true },
+            DismissReason::ClickOutside,
+        ),
+        (
+            Interaction::ClickOutside { within_app: false },
+            DismissReason::ClickOutside,
+        ),
+        (
+            Interaction::FocusLost { within_app: true },
+            DismissReason::FocusLost,
+        ),
+        (Interaction::Escape, DismissReason::Escape),
+    ];
+
+    for (interaction, reason) in cases {
+        assert_eq!(
+            PopoverBehavior::Transient.dismiss_reason(interaction),
+            Some(reason)
+        );
+    }
+}
+
+#[test]
+fn it_keeps_semitransient_popovers_open_within_the_app() {
+    let behavior = PopoverBehavior::Semitransient;
+
+    assert_eq!(
+        behavior.dismiss_reason(Interaction::ClickOutside { within_app: true }),
+        None
+    );
+
+    assert_eq!(
+        behavior.dismiss_reason(Interaction::FocusLost { within_app: true }),
+        None
+    );
+
+    assert_eq!(
+        behavior.dismiss_reason(Interaction::FocusLost { within_app: false }),
+        Some(DismissReason::FocusLost)
+    );
+
+    assert_eq!(
+        behavior.dismiss_reason(Interaction::Escape),
+        Some(DismissReason::Escape)
+    );
+}
+
+#[test]
+fn it_serializes_the_dismissed_event() {
+    let event = DismissedEvent {
+        label: "main".to_string(),
+        reason: DismissReason::ClickOutside,
+    };
+
+    assert_eq!(
+        serde_json::to_string(&event).unwrap(),
+        r#"{"label":"main","reason":"clickOutside"}"#
+    );
+
+    assert_eq!(
+        serde_json::from_str::<PopoverBehavior>(r#""semitransient""#).unwrap(),
+        PopoverBehavior::Semitransient
+    );
+}
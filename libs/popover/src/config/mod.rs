@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use tauri::window::Color;
+
+use crate::{
+    animation::PopoverAnimation,
+    behavior::PopoverBehavior,
+    position::PositionOptions,
+    shape::{arrow_height, ArrowEdge, ShapeOptions},
+};
+
+mod tests;
+
+/// The key of the popover config in the `plugins` section of `tauri.conf.json`.
+const CONFIG_KEY: &str = "popover";
+
+/// Distances from each edge of a popover to its content.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(default)]
+pub struct EdgeInsets {
+    pub top: f64,
+    pub left: f64,
+    pub bottom: f64,
+    pub right: f64,
+}
+
+impl EdgeInsets {
+    pub fn new(top: f64, left: f64, bottom: f64, right: f64) -> Self {
+        Self {
+            top,
+            left,
+            bottom,
+            right,
+        }
+    }
+}
+
+/// A color the OS provides, which follows the app's light or dark appearance.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SystemColor {
+    WindowBackground,
+    ControlBackground,
+    UnderPageBackground,
+    Separator,
+    Label,
+    SecondaryLabel,
+    ControlAccent,
+}
+
+impl SystemColor {
+    /// The color in a light appearance, for when the platform does not provide it.
+    pub fn fallback(&self) -> Color {
+        match self {
+            Self::WindowBackground => Color(236, 236, 236, 255),
+            Self::ControlBackground => Color(255, 255, 255, 255),
+            Self::UnderPageBackground => Color(150, 150, 150, 230),
+            Self::Separator => Color(0, 0, 0, 25),
+            Self::Label => Color(0, 0, 0, 216),
+            Self::SecondaryLabel => Color(0, 0, 0, 128),
+            Self::ControlAccent => Color(0, 122, 255, 255),
+        }
+    }
+}
+
+/// A color given as RGBA components, e.g. `[255, 255, 255, 26]`, or as the name of a
+/// system color, e.g. `"windowBackground"`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PopoverColor {
+    Rgba(Color),
+    System(SystemColor),
+}
+
+impl From<Color> for PopoverColor {
+    fn from(color: Color) -> Self {
+        Self::Rgba(color)
+    }
+}
+
+impl From<SystemColor> for PopoverColor {
+    fn from(color: SystemColor) -> Self {
+        Self::System(color)
+    }
+}
+
+/// How a popover looks and where it sits relative to its anchor. Distances are in the
+/// coordinate space windows are positioned in: logical points on macOS and physical
+/// pixels elsewhere. Missing fields take their default when deserialized.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PopoverConfig {
+    pub animation: PopoverAnimation,
+    pub arrow_edge: ArrowEdge,
+    pub arrow_height: f64,
+    /// The position of the arrow's tip along its edge, from the left on the top and bottom
+    /// edges and from the top on the side ones.
+    pub arrow_position: f64,
+    pub arrow_width: f64,
+    pub background_color: PopoverColor,
+    pub behavior: PopoverBehavior,
+    /// The color of the border, which is not drawn when `None`.
+    pub border_color: Option<PopoverColor>,
+    pub border_width: f64,
+    pub content_edge_insets: EdgeInsets,
+    pub corner_radius: f64,
+    pub popover_to_status_item_margin: f64,
+    pub right_edge_margin: f64,
+}
+
+impl Default for PopoverConfig {
+    fn default() -> Self {
+        Self {
+            animation: PopoverAnimation::default(),
+            arrow_edge: ArrowEdge::Top,
+            arrow_height: 12.0,
+            arrow_position: 0.0,
+            arrow_width: 62.0,
+            background_color: SystemColor::WindowBackground.into(),
+            behavior: PopoverBehavior::ApplicationDefined,
+            border_color: Some(Color(255, 255, 255, 26).into()),
+            border_width: 2.0,
+            content_edge_insets: EdgeInsets::default(),
+            corner_radius: 12.0,
+            popover_to_status_item_margin: 2.0,
+            right_edge_margin: 12.0,
+        }
+    }
+}
+
+impl PopoverConfig {
+    /// Reads the config under `plugins.popover` in `tauri.conf.json`, or `None` if there
+    /// is none.
+    pub fn from_tauri_config(config: &tauri::Config) -> serde_json::Result<Option<Self>> {
+        config
+            .plugins
+            .0
+            .get(CONFIG_KEY)
+            .map(|value| serde_json::from_value(value.clone()))
+            .transpose()
+    }
+
+    /// The distances from each edge of the popover to the webview: the border, the arrow
+    /// on its edge, and `content_edge_insets`. These stay the same as the window resizes.
+    pub fn content_insets(&self) -> EdgeInsets {
+        let arrow_height = arrow_height(&ShapeOptions::from(self));
+
+        // Arrows are drawn on the top edge until an `Auto` one is resolved.
+        let arrow_edge = match self.arrow_edge {
+            ArrowEdge::Auto => ArrowEdge::Top,
+            edge => edge,
+        };
+
+        let inset = |edge: ArrowEdge, inset: f64| {
+            let arrow = if arrow_edge == edge {
+                arrow_height
+            } else {
+                0.0
+            };
+
+            self.border_width + arrow + inset
+        };
+
+        let insets = &self.content_edge_insets;
+
+        EdgeInsets::new(
+            inset(ArrowEdge::Top, insets.top),
+            inset(ArrowEdge::Left, insets.left),
+            inset(ArrowEdge::Bottom, insets.bottom),
+            inset(ArrowEdge::Right, insets.right),
+        )
+    }
+}
+
+impl From<&PopoverConfig> for PositionOptions {
+    fn from(config: &PopoverConfig) -> Self {
+        Self {
+            popover_to_status_item_margin: config.popover_to_status_item_margin,
+            right_edge_margin: config.right_edge_margin,
+            arrow_edge: config.arrow_edge,
+            arrow_width: config.arrow_width,
+            corner_radius: config.corner_radius,
+            border_width: config.border_width,
+        }
+    }
+}
+
+impl From<&PopoverConfig> for ShapeOptions {
+    fn from(config: &PopoverConfig) -> Self {
+        Self {
+            arrow_edge: config.arrow_edge,
+            arrow_height: config.arrow_height,
+            arrow_width: config.arrow_width,
+            arrow_position: config.arrow_position,
+            border_width: config.border_width,
+            corner_radius: config.corner_radius,
+        }
+    }
+}
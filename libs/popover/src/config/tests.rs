@@ -0,0 +1,103 @@
+#![cfg(test)]
+
+use tauri::window::Color;
+
+use crate::shape::ArrowEdge;
+
+use super::{EdgeInsets, PopoverColor, PopoverConfig, SystemColor};
+
+#[test]
+fn it_fills_in_missing_fields_with_defaults() {
+    let config: PopoverConfig = serde_json::from_str(
+        r#"{
+            "arrowEdge": "auto",
+            "cornerRadius": 8,
+            "contentEdgeInsets": { "top": 4 }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config,
+        PopoverConfig {
+            arrow_edge: ArrowEdge::Auto,
+            corner_radius: 8.0,
+            content_edge_insets: EdgeInsets::new(4.0, 0.0, 0.0, 0.0),
+            ..PopoverConfig::default()
+        }
+    );
+}
+
+#[test]
+fn it_reads_colors_as_components_or_system_names() {
+    let config: PopoverConfig = serde_json::from_str(
+        r#"{
+            "backgroundColor": [30, 30, 30, 255],
+            "borderColor": "separator"
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.background_color,
+        PopoverColor::Rgba(Color(30, 30, 30, 255))
+    );
+
+    assert_eq!(
+        config.border_color,
+        Some(PopoverColor::System(SystemColor::Separator))
+    );
+
+    let config: PopoverConfig = serde_json::from_str(r#"{ "borderColor": null }"#).unwrap();
+
+    assert_eq!(config.border_color, None);
+}
+
+#[test]
+fn it_round_trips_through_json() {
+    let config = PopoverConfig {
+        arrow_edge: ArrowEdge::Bottom,
+        background_color: Color(255, 0, 0, 128).into(),
+        ..PopoverConfig::default()
+    };
+
+    let json = serde_json::to_value(&config).unwrap();
+
+    assert_eq!(json["arrowEdge"], "bottom");
+    assert_eq!(json["backgroundColor"], serde_json::json!([255, 0, 0, 128]));
+
+    assert_eq!(
+        serde_json::from_value::<PopoverConfig>(json).unwrap(),
+        config
+    );
+}
+
+#[test]
+fn it_insets_the_content_by_the_border_arrow_and_insets() {
+    let config = PopoverConfig {
+        content_edge_insets: EdgeInsets::new(4.0, 6.0, 8.0, 10.0),
+        ..PopoverConfig::default()
+    };
+
+    assert_eq!(
+        config.content_insets(),
+        EdgeInsets::new(18.0, 8.0, 10.0, 12.0)
+    );
+
+    let config = PopoverConfig {
+        arrow_edge: ArrowEdge::Right,
+        ..PopoverConfig::default()
+    };
+
+    assert_eq!(
+        config.content_insets(),
+        EdgeInsets::new(2.0, 2.0, 2.0, 14.0)
+    );
+
+    let config = PopoverConfig {
+        arrow_edge: ArrowEdge::None,
+        ..PopoverConfig::default()
+    };
+
+    assert_eq!(config.content_insets(), EdgeInsets::new(2.0, 2.0, 2.0, 2.0));
+}
@@ -1,66 +1,72 @@
-#[cfg(target_os = "macos")]
-pub mod macos;
+#[cfg(target_os = "linux")]
+mod linux;
 
 #[cfg(target_os = "macos")]
-use crate::macos::popover::PopoverConfig;
-
-#[cfg(target_os = "windows")]
-pub fn add_view(window: &tauri::WebviewWindow) {
-    unimplemented!();
-}
+pub mod macos;
 
-#[cfg(target_os = "linux")]
-pub fn add_view(window: &tauri::WebviewWindow) {
-    unimplemented!();
+mod animation;
+mod behavior;
+mod config;
+mod position;
+mod shape;
+mod window;
+
+use monitor::Rect;
+
+pub use animation::{AnimationFrame, AnimationStyle, Easing, PopoverAnimation};
+pub use behavior::{DismissReason, DismissedEvent, Interaction, PopoverBehavior, DISMISSED_EVENT};
+pub use config::{EdgeInsets, PopoverColor, PopoverConfig, SystemColor};
+pub use position::{position, Placement, PositionOptions};
+pub use shape::{
+    arrow_tip, content_rect, outline, ArrowEdge, Path, PathCommand, Point, ShapeOptions,
+};
+pub use window::{Popover, PopoverError, WebviewWindowExt};
+
+/// Adds a popover view to `window`. Use `WebviewWindowExt::add_popover` to get a handle
+/// to it.
+pub fn add_view(window: &tauri::WebviewWindow, options: Option<PopoverConfig>) {
+    window.add_popover(options).unwrap();
 }
 
-#[cfg(target_os = "macos")]
-pub fn add_view(window: &tauri::WebviewWindow, options: Option<PopoverConfig>) {
-    use cocoa::{
-        base::id,
-        foundation::{NSInteger, NSPoint, NSRect, NSSize},
+/// Moves `window` beside `anchor`, e.g. the frame of a tray icon, on the side its arrow
+/// edge faces, within the visible area of the monitor it is on, and points the popover
+/// arrow at it.
+pub fn position_window(
+    window: &tauri::WebviewWindow,
+    anchor: &Rect,
+    options: &PositionOptions,
+) -> tauri::Result<Placement> {
+    let size = window.outer_size()?;
+
+    // Only macOS positions windows in logical points.
+    let (width, height) = if cfg!(target_os = "macos") {
+        let size = size.to_logical::<f64>(window.scale_factor()?);
+
+        (size.width, size.height)
+    } else {
+        (size.width as f64, size.height as f64)
     };
-    use objc::{msg_send, sel, sel_impl};
-    use tauri::Manager;
-
-    use crate::macos::popover::PopoverView;
-
-    #[allow(non_upper_case_globals)]
-    const NSWindowAnimationBehaviorUtilityWindow: NSInteger = 4;
-
-    let win = window.clone();
-
-    window
-        .app_handle()
-        .run_on_main_thread(move || {
-            let handle: id = win.ns_window().unwrap() as _;
-
-            let content_frame: NSRect = unsafe { msg_send![handle, frame] };
-
-            let content_view: id = unsafe { msg_send![handle, contentView] };
-
-            let mut config = options.unwrap_or_default();
-
-            if options.is_none() {
-                config.arrow_position = content_frame.size.width / 2.0;
-            }
-
-            let view = PopoverView::new(config);
 
-            let _frame = NSRect::new(
-                NSPoint::new(0.0, 0.0),
-                NSSize::new(content_frame.size.width, content_frame.size.height),
-            );
+    let placement = position(
+        anchor,
+        width,
+        height,
+        &position::visible_area_for(anchor),
+        options,
+    );
 
-            view.set_frame(_frame);
+    #[cfg(target_os = "macos")]
+    window.set_position(tauri::LogicalPosition::new(placement.x, placement.y))?;
 
-            view.set_parent(content_view);
+    #[cfg(not(target_os = "macos"))]
+    window.set_position(tauri::PhysicalPosition::new(placement.x, placement.y))?;
 
-            view.set_autoresizing();
+    if let Some(popover) = window.popover() {
+        match popover.place_arrow(placement.arrow_edge, placement.arrow_position) {
+            Ok(()) | Err(PopoverError::NotFound) => {}
+            Err(PopoverError::Tauri(error)) => return Err(error),
+        }
+    }
 
-            let () = unsafe {
-                msg_send![handle, setAnimationBehavior: NSWindowAnimationBehaviorUtilityWindow]
-            };
-        })
-        .unwrap();
+    Ok(placement)
 }
@@ -0,0 +1,432 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use gtk::{cairo, gdk, glib, glib::SignalHandlerId, prelude::*, ApplicationWindow};
+use monitor::Rect;
+use tauri::{window::Color, Runtime, WebviewWindow};
+
+use crate::{
+    animation::AnimationFrame,
+    behavior::Interaction,
+    config::{EdgeInsets, PopoverColor, PopoverConfig, SystemColor},
+    shape::{outline, PathCommand, Point, ShapeOptions},
+    window::{drawn, interact, Attachments},
+};
+
+/// The signal handlers a popover connected to its window, whether the window was app
+/// paintable before, and whether the popover holds the pointer grab.
+struct Attached {
+    handlers: Vec<SignalHandlerId>,
+    was_app_paintable: bool,
+    grabbed: Rc<Cell<bool>>,
+}
+
+thread_local! {
+    static ATTACHED: RefCell<Attachments<Attached>> = RefCell::new(Attachments::new());
+}
+
+/// The GTK theme color standing in for `color`.
+fn theme_color_name(color: SystemColor) -> &'static str {
+    match color {
+        SystemColor::WindowBackground => "theme_bg_color",
+        SystemColor::ControlBackground => "theme_base_color",
+        SystemColor::UnderPageBackground => "theme_unfocused_bg_color",
+        SystemColor::Separator => "borders",
+        SystemColor::Label => "theme_fg_color",
+        SystemColor::SecondaryLabel => "insensitive_fg_color",
+        SystemColor::ControlAccent => "theme_selected_bg_color",
+    }
+}
+
+fn set_source(cr: &cairo::Context, widget: &ApplicationWindow, color: &PopoverColor) {
+    let from_components = |color: &Color| {
+        (
+            color.0 as f64 / 255.0,
+            color.1 as f64 / 255.0,
+            color.2 as f64 / 255.0,
+            color.3 as f64 / 255.0,
+        )
+    };
+
+    let (red, green, blue, alpha) = match color {
+        PopoverColor::Rgba(color) => from_components(color),
+        PopoverColor::System(color) => widget
+            .style_context()
+            .lookup_color(theme_color_name(*color))
+            .map(|rgba| (rgba.red(), rgba.green(), rgba.blue(), rgba.alpha()))
+            .unwrap_or_else(|| from_components(&color.fallback())),
+    };
+
+    cr.set_source_rgba(red, green, blue, alpha);
+}
+
+/// The window's frame in physical pixels, which popover distances are in, and the
+/// number of physical pixels per GTK unit.
+fn physical_frame(widget: &ApplicationWindow) -> (Rect, f64) {
+    let scale = widget.scale_factor().max(1) as f64;
+
+    let frame = Rect::new(
+        0.0,
+        0.0,
+        widget.allocated_width() as f64 * scale,
+        widget.allocated_height() as f64 * scale,
+    );
+
+    (frame, scale)
+}
+
+fn draw(widget: &ApplicationWindow, cr: &cairo::Context, config: &PopoverConfig) {
+    let (frame, scale) = physical_frame(widget);
+
+    let path = outline(&frame, &ShapeOptions::from(config));
+
+    // Clears what the window drew before, so the corners are transparent.
+    cr.set_operator(cairo::Operator::Source);
+
+    cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
+
+    let _ = cr.paint();
+
+    cr.set_operator(cairo::Operator::Over);
+
+    let _ = cr.save();
+
+    cr.scale(1.0 / scale, 1.0 / scale);
+
+    cr.new_path();
+
+    for command in path.commands() {
+        match command {
+            PathCommand::MoveTo(point) => cr.move_to(point.x, point.y),
+            PathCommand::LineTo(point) => cr.line_to(point.x, point.y),
+            PathCommand::CurveTo {
+                to,
+                control1,
+                control2,
+            } => cr.curve_to(control1.x, control1.y, control2.x, control2.y, to.x, to.y),
+            PathCommand::Close => cr.close_path(),
+        }
+    }
+
+    if let Some(border_color) = &config.border_color {
+        set_source(cr, widget, border_color);
+
+        cr.set_line_width(config.border_width);
+
+        let _ = cr.stroke_preserve();
+    }
+
+    set_source(cr, widget, &config.background_color);
+
+    let _ = cr.fill();
+
+    let _ = cr.restore();
+}
+
+/// Makes the window outside the outline click-through, and, without a compositor to
+/// blend its transparent corners, cuts them out of the window.
+fn update_shape(widget: &ApplicationWindow, config: &PopoverConfig) {
+    let (frame, scale) = physical_frame(widget);
+
+    let rects: Vec<cairo::RectangleInt> = outline(&frame, &ShapeOptions::from(config))
+        .pixel_rects()
+        .iter()
+        .map(|rect| {
+            let x = (rect.x / scale).floor();
+
+            let y = (rect.y / scale).floor();
+
+            cairo::RectangleInt::new(
+                x as i32,
+                y as i32,
+                ((rect.right() / scale).ceil() - x) as i32,
+                ((rect.bottom() / scale).ceil() - y) as i32,
+            )
+        })
+        .collect();
+
+    let region = cairo::Region::create_rectangles(&rects);
+
+    widget.input_shape_combine_region(Some(&region));
+
+    let composited = widget
+        .screen()
+        .map_or(false, |screen| screen.is_composited());
+
+    if composited {
+        widget.shape_combine_region(None);
+    } else {
+        widget.shape_combine_region(Some(&region));
+    }
+}
+
+/// Insets the webview of `window` by `insets`, given in physical pixels, with margins on
+/// the box holding it, which it keeps as the window resizes. Runs on the main thread.
+fn layout_content<R: Runtime>(
+    window: &WebviewWindow<R>,
+    gtk_window: &ApplicationWindow,
+    insets: EdgeInsets,
+) {
+    let vbox = match window.default_vbox() {
+        Ok(vbox) => vbox,
+        Err(_) => return,
+    };
+
+    let scale = gtk_window.scale_factor().max(1) as f64;
+
+    // Rounds up, so the webview never covers the border.
+    let margin = |inset: f64| (inset / scale).ceil().max(0.0) as i32;
+
+    vbox.set_margin_top(margin(insets.top));
+
+    vbox.set_margin_start(margin(insets.left));
+
+    vbox.set_margin_bottom(margin(insets.bottom));
+
+    vbox.set_margin_end(margin(insets.right));
+}
+
+/// Whether the popover of the window `label` closes on a click outside the app, which it
+/// only sees while it holds the pointer grab.
+fn closes_on_click_outside(label: &str) -> bool {
+    drawn(label).map_or(false, |config| {
+        config
+            .behavior
+            .dismiss_reason(Interaction::ClickOutside { within_app: false })
+            .is_some()
+    })
+}
+
+/// Grabs the pointer for `gtk_window`, so clicks outside the app are reported to it.
+/// Clicks on the app's windows still reach them. Returns whether the grab was taken,
+/// which fails on Wayland for windows other than popups, or while another client holds
+/// a grab.
+fn grab_pointer(gtk_window: &ApplicationWindow) -> bool {
+    match (gtk_window.window(), gtk_window.display().default_seat()) {
+        (Some(gdk_window), Some(seat)) => {
+            seat.grab(
+                &gdk_window,
+                gdk::SeatCapabilities::ALL_POINTING,
+                true,
+                None,
+                None,
+                None,
+            ) == gdk::GrabStatus::Success
+        }
+        _ => false,
+    }
+}
+
+fn ungrab_pointer(gtk_window: &ApplicationWindow, grabbed: &Cell<bool>) {
+    if grabbed.replace(false) {
+        if let Some(seat) = gtk_window.display().default_seat() {
+            seat.ungrab();
+        }
+    }
+}
+
+/// Whether `event`, reported to `gtk_window` while it holds the grab, landed outside it.
+fn is_outside(gtk_window: &ApplicationWindow, event: &gdk::EventButton) -> bool {
+    let frame = match gtk_window.window() {
+        Some(gdk_window) => gdk_window.frame_extents(),
+        None => return false,
+    };
+
+    let (x, y) = event.root();
+
+    x < frame.x() as f64
+        || y < frame.y() as f64
+        || x >= (frame.x() + frame.width()) as f64
+        || y >= (frame.y() + frame.height()) as f64
+}
+
+/// Draws the popover described by `config` behind the webview of `window`, and reports
+/// Escape presses and clicks outside the app to its behavior. The window needs to be
+/// transparent for the webview to show it, and is inset to the popover's content rect.
+/// Clicks outside the app are seen through a pointer grab taken while the window is
+/// shown, when the behavior closes the popover on them.
+pub fn attach<R: Runtime>(window: &WebviewWindow<R>, config: PopoverConfig) -> tauri::Result<()> {
+    let win = window.clone();
+
+    window.run_on_main_thread(move || {
+        let gtk_window = match win.gtk_window() {
+            Ok(gtk_window) => gtk_window,
+            Err(_) => return,
+        };
+
+        let label = win.label().to_string();
+
+        let was_app_paintable = gtk_window.is_app_paintable();
+
+        // Keeps GTK from painting the window's background over the popover.
+        gtk_window.set_app_paintable(true);
+
+        let draw_label = label.clone();
+
+        let draw_handler = gtk_window.connect_draw(move |widget, cr| {
+            if let Some(config) = drawn(&draw_label) {
+                draw(widget, cr, &config);
+            }
+
+            glib::Propagation::Proceed
+        });
+
+        let resize_window = win.clone();
+
+        // Also follows the scale factor, which the webview's margins depend on.
+        let resize_handler = gtk_window.connect_size_allocate(move |widget, _| {
+            if let Some(config) = drawn(resize_window.label()) {
+                update_shape(widget, &config);
+
+                layout_content(&resize_window, widget, config.content_insets());
+            }
+        });
+
+        let key_window = win.clone();
+
+        let key_handler = gtk_window.connect_key_press_event(move |_, event| {
+            if event.keyval() == gdk::keys::constants::Escape {
+                interact(&key_window, Interaction::Escape);
+            }
+
+            glib::Propagation::Proceed
+        });
+
+        let grabbed = Rc::new(Cell::new(false));
+
+        let map_label = label.clone();
+
+        let map_grabbed = grabbed.clone();
+
+        let map_handler = gtk_window.connect_map_event(move |widget, _| {
+            if closes_on_click_outside(&map_label) {
+                map_grabbed.set(grab_pointer(widget));
+            }
+
+            glib::Propagation::Proceed
+        });
+
+        let unmap_grabbed = grabbed.clone();
+
+        let unmap_handler = gtk_window.connect_unmap_event(move |widget, _| {
+            ungrab_pointer(widget, &unmap_grabbed);
+
+            glib::Propagation::Proceed
+        });
+
+        let click_window = win.clone();
+
+        let click_grabbed = grabbed.clone();
+
+        // While the grab is held, clicks outside the app are reported to this window.
+        let click_handler = gtk_window.connect_button_press_event(move |widget, event| {
+            if click_grabbed.get() && is_outside(widget, event) {
+                interact(
+                    &click_window,
+                    Interaction::ClickOutside { within_app: false },
+                );
+            }
+
+            glib::Propagation::Proceed
+        });
+
+        gtk_window.add_events(gdk::EventMask::BUTTON_PRESS_MASK);
+
+        // An entry left by a destroyed window with the same label is dropped, since its
+        // handlers went with that window.
+        ATTACHED.with(|attached| {
+            attached.borrow_mut().insert(
+                &label,
+                Attached {
+                    handlers: vec![
+                        draw_handler,
+                        resize_handler,
+                        key_handler,
+                        map_handler,
+                        unmap_handler,
+                        click_handler,
+                    ],
+                    was_app_paintable,
+                    grabbed,
+                },
+            )
+        });
+
+        update_shape(&gtk_window, &config);
+
+        layout_content(&win, &gtk_window, config.content_insets());
+
+        gtk_window.queue_draw();
+    })
+}
+
+/// Redraws the popover of `window`, which draws from its current config, and lays
+/// out its webview again, since the arrow may have moved to another edge.
+pub fn apply<R: Runtime>(window: &WebviewWindow<R>, config: PopoverConfig) -> tauri::Result<()> {
+    let win = window.clone();
+
+    window.run_on_main_thread(move || {
+        if let Ok(gtk_window) = win.gtk_window() {
+            update_shape(&gtk_window, &config);
+
+            layout_content(&win, &gtk_window, config.content_insets());
+
+            gtk_window.queue_draw();
+        }
+    })
+}
+
+/// Stops drawing the popover of `window`, and restores its shape and the webview's
+/// layout.
+pub fn detach<R: Runtime>(window: &WebviewWindow<R>) -> tauri::Result<()> {
+    let win = window.clone();
+
+    window.run_on_main_thread(move || {
+        let gtk_window = match win.gtk_window() {
+            Ok(gtk_window) => gtk_window,
+            Err(_) => return,
+        };
+
+        let attached = ATTACHED.with(|attached| attached.borrow_mut().remove(win.label()));
+
+        if let Some(attached) = attached {
+            for handler in attached.handlers {
+                gtk_window.disconnect(handler);
+            }
+
+            gtk_window.set_app_paintable(attached.was_app_paintable);
+
+            ungrab_pointer(&gtk_window, &attached.grabbed);
+        }
+
+        gtk_window.input_shape_combine_region(None);
+
+        gtk_window.shape_combine_region(None);
+
+        layout_content(&win, &gtk_window, EdgeInsets::default());
+
+        gtk_window.queue_draw();
+    })
+}
+
+/// Forgets the signal handlers of the destroyed window `label`, which went with it.
+pub fn release(label: &str) {
+    ATTACHED.with(|attached| attached.borrow_mut().remove(label));
+}
+
+/// Whether animations are turned off in the GTK settings, which desktops set when the
+/// user asks to reduce motion. Runs on the main thread.
+pub fn prefers_reduced_motion() -> bool {
+    gtk::Settings::default().map_or(false, |settings| !settings.is_gtk_enable_animations())
+}
+
+/// Draws `frame` of an animation of the popover of `window`. GTK cannot scale a window's
+/// content, so only the opacity is animated, which needs a compositing manager. Runs on
+/// the main thread.
+pub fn render<R: Runtime>(window: &WebviewWindow<R>, frame: &AnimationFrame, _origin: Point) {
+    if let Ok(gtk_window) = window.gtk_window() {
+        gtk_window.set_opacity(frame.opacity);
+    }
+}
@@ -1,66 +1,141 @@
+use std::cell::RefCell;
+
+use block::ConcreteBlock;
 use cocoa::{
     appkit::{CGFloat, NSViewHeightSizable, NSViewWidthSizable, NSWindowOrderingMode},
-    base::id,
-    foundation::{NSPoint, NSRect, NSSize},
+    base::{id, nil, BOOL, YES},
+    foundation::{NSInteger, NSPoint, NSRect, NSSize, NSUInteger},
 };
 use objc::{
     class,
     declare::ClassDecl,
     msg_send,
     runtime::{Class, Object, Sel},
-    sel, sel_impl, Message,
+    sel, sel_impl, Encode, Encoding, Message,
 };
 
 use objc2_app_kit::NSBezierPath;
 
 use objc_id::Id;
 
-use objc2_foundation::{CGPoint, CGRect, CGSize, NSEdgeInsets, NSEdgeInsetsZero};
+use objc2_foundation::CGPoint;
 
 use objc_foundation::INSObject;
 
+use color::ColorExt;
+
+use monitor::Rect;
+use tauri::{Runtime, WebviewWindow};
+
+use crate::{
+    animation::AnimationFrame,
+    behavior::Interaction,
+    config::{EdgeInsets, PopoverColor, PopoverConfig, SystemColor},
+    shape::{outline, ArrowEdge, PathCommand, Point, ShapeOptions},
+    window::{interact, Attachments},
+};
+
 const CLS_NAME: &str = "PopoverView";
 
-#[derive(Copy, Clone)]
-pub struct PopoverConfig {
-    pub arrow_height: CGFloat,
-    pub arrow_position: CGFloat,
-    pub arrow_width: CGFloat,
-    pub background_color: id,
-    pub border_color: id,
-    pub border_width: CGFloat,
-    pub content_edge_insets: NSEdgeInsets,
-    pub corner_radius: CGFloat,
-    pub popover_to_status_item_margin: CGFloat,
-    pub right_edge_margin: CGFloat,
+#[allow(non_upper_case_globals)]
+const NSWindowAnimationBehaviorNone: NSInteger = 2;
+
+#[allow(non_upper_case_globals)]
+const NSWindowAnimationBehaviorUtilityWindow: NSInteger = 4;
+
+#[allow(non_upper_case_globals)]
+const NSEventTypeKeyDown: NSUInteger = 10;
+
+/// Left, right and other mouse down events.
+const CLICK_EVENTS_MASK: NSUInteger = 1 << 1 | 1 << 3 | 1 << 25;
+
+const KEY_DOWN_EVENTS_MASK: NSUInteger = 1 << NSEventTypeKeyDown;
+
+const ESCAPE_KEY_CODE: u16 = 53;
+
+thread_local! {
+    /// The event monitors added for each popover.
+    static MONITORS: RefCell<Attachments<Vec<id>>> = RefCell::new(Attachments::new());
 }
 
-impl Default for PopoverConfig {
-    fn default() -> Self {
-        let background_color: id = unsafe { msg_send![class!(NSColor), windowBackgroundColor] };
-
-        let border_color: id = unsafe { msg_send![class!(NSColor), whiteColor] };
-
-        let border_color: id = unsafe { msg_send![border_color, colorWithAlphaComponent: 0.1] };
-
-        Self {
-            popover_to_status_item_margin: 2.0,
-            background_color,
-            border_color,
-            border_width: 2.0,
-            arrow_width: 62.0,
-            arrow_height: 12.0,
-            arrow_position: 0.0,
-            corner_radius: 12.0,
-            content_edge_insets: unsafe { NSEdgeInsetsZero },
-            right_edge_margin: 12.0,
-        }
+/// An affine transform, which layers are scaled with.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CGAffineTransform {
+    a: CGFloat,
+    b: CGFloat,
+    c: CGFloat,
+    d: CGFloat,
+    tx: CGFloat,
+    ty: CGFloat,
+}
+
+unsafe impl Encode for CGAffineTransform {
+    fn encode() -> Encoding {
+        unsafe { Encoding::from_str("{CGAffineTransform=dddddd}") }
     }
 }
 
-unsafe impl Sync for PopoverConfig {}
+/// Makes an `NSColor` for `color`.
+fn ns_color(color: &PopoverColor) -> id {
+    match color {
+        PopoverColor::Rgba(color) => color.to_nscolor(),
+        PopoverColor::System(color) => unsafe {
+            match color {
+                SystemColor::WindowBackground => msg_send![class!(NSColor), windowBackgroundColor],
+                SystemColor::ControlBackground => {
+                    msg_send![class!(NSColor), controlBackgroundColor]
+                }
+                SystemColor::UnderPageBackground => {
+                    msg_send![class!(NSColor), underPageBackgroundColor]
+                }
+                SystemColor::Separator => msg_send![class!(NSColor), separatorColor],
+                SystemColor::Label => msg_send![class!(NSColor), labelColor],
+                SystemColor::SecondaryLabel => msg_send![class!(NSColor), secondaryLabelColor],
+                SystemColor::ControlAccent => msg_send![class!(NSColor), controlAccentColor],
+            }
+        },
+    }
+}
 
-unsafe impl Send for PopoverConfig {}
+/// Stores `value` in the object ivar `name`, retaining it and releasing the previous one,
+/// since the colors set on the view are autoreleased.
+unsafe fn set_retained_ivar(this: &mut Object, name: &str, value: id) {
+    let previous = *this.get_ivar::<id>(name);
+
+    if !value.is_null() {
+        let _: id = msg_send![value, retain];
+    }
+
+    this.set_ivar::<id>(name, value);
+
+    if !previous.is_null() {
+        let () = msg_send![previous, release];
+    }
+}
+
+/// Arrow edges are kept in an `NSInteger` ivar.
+fn arrow_edge_to_raw(edge: ArrowEdge) -> NSInteger {
+    match edge {
+        ArrowEdge::Top => 0,
+        ArrowEdge::Bottom => 1,
+        ArrowEdge::Left => 2,
+        ArrowEdge::Right => 3,
+        ArrowEdge::None => 4,
+        ArrowEdge::Auto => 5,
+    }
+}
+
+fn arrow_edge_from_raw(raw: NSInteger) -> ArrowEdge {
+    match raw {
+        1 => ArrowEdge::Bottom,
+        2 => ArrowEdge::Left,
+        3 => ArrowEdge::Right,
+        4 => ArrowEdge::None,
+        5 => ArrowEdge::Auto,
+        _ => ArrowEdge::Top,
+    }
+}
 
 pub struct PopoverView;
 
@@ -81,6 +156,8 @@ impl PopoverView {
 
         decl.add_ivar::<CGFloat>("border_width");
 
+        decl.add_ivar::<NSInteger>("arrow_edge");
+
         decl.add_ivar::<CGFloat>("arrow_height");
 
         decl.add_ivar::<CGFloat>("arrow_width");
@@ -89,7 +166,13 @@ impl PopoverView {
 
         decl.add_ivar::<CGFloat>("corner_radius");
 
-        decl.add_ivar::<id>("content_edge_insets");
+        decl.add_ivar::<CGFloat>("content_inset_top");
+
+        decl.add_ivar::<CGFloat>("content_inset_left");
+
+        decl.add_ivar::<CGFloat>("content_inset_bottom");
+
+        decl.add_ivar::<CGFloat>("content_inset_right");
 
         decl.add_ivar::<CGFloat>("right_edge_margin");
 
@@ -120,6 +203,11 @@ impl PopoverView {
                 Self::handle_set_border_width as extern "C" fn(&mut Object, Sel, CGFloat),
             );
 
+            decl.add_method(
+                sel!(setArrowEdge:),
+                Self::handle_set_arrow_edge as extern "C" fn(&mut Object, Sel, NSInteger),
+            );
+
             decl.add_method(
                 sel!(setArrowHeight:),
                 Self::handle_set_arrow_height as extern "C" fn(&mut Object, Sel, CGFloat),
@@ -141,8 +229,9 @@ impl PopoverView {
             );
 
             decl.add_method(
-                sel!(setContentEdgeInsets:),
-                Self::handle_set_content_edge_insets as extern "C" fn(&mut Object, Sel, id),
+                sel!(setContentEdgeInsetsTop:left:bottom:right:),
+                Self::handle_set_content_edge_insets
+                    as extern "C" fn(&mut Object, Sel, CGFloat, CGFloat, CGFloat, CGFloat),
             );
 
             decl.add_method(
@@ -163,17 +252,21 @@ impl PopoverView {
     }
 
     extern "C" fn handle_set_background_color(this: &mut Object, _: Sel, ns_color: id) {
-        unsafe { this.set_ivar::<id>("background_color", ns_color) };
+        unsafe { set_retained_ivar(this, "background_color", ns_color) };
     }
 
     extern "C" fn handle_set_border_color(this: &mut Object, _: Sel, ns_color: id) {
-        unsafe { this.set_ivar::<id>("border_color", ns_color) };
+        unsafe { set_retained_ivar(this, "border_color", ns_color) };
     }
 
     extern "C" fn handle_set_border_width(this: &mut Object, _: Sel, value: CGFloat) {
         unsafe { this.set_ivar::<CGFloat>("border_width", value) };
     }
 
+    extern "C" fn handle_set_arrow_edge(this: &mut Object, _: Sel, value: NSInteger) {
+        unsafe { this.set_ivar::<NSInteger>("arrow_edge", value) };
+    }
+
     extern "C" fn handle_set_arrow_height(this: &mut Object, _: Sel, value: CGFloat) {
         unsafe { this.set_ivar::<CGFloat>("arrow_height", value) };
     }
@@ -190,162 +283,79 @@ impl PopoverView {
         unsafe { this.set_ivar::<CGFloat>("corner_radius", value) };
     }
 
-    extern "C" fn handle_set_content_edge_insets(this: &mut Object, _: Sel, ns_edge_insets: id) {
-        unsafe { this.set_ivar::<id>("content_edge_insets", ns_edge_insets) };
+    extern "C" fn handle_set_content_edge_insets(
+        this: &mut Object,
+        _: Sel,
+        top: CGFloat,
+        left: CGFloat,
+        bottom: CGFloat,
+        right: CGFloat,
+    ) {
+        unsafe {
+            this.set_ivar::<CGFloat>("content_inset_top", top);
+
+            this.set_ivar::<CGFloat>("content_inset_left", left);
+
+            this.set_ivar::<CGFloat>("content_inset_bottom", bottom);
+
+            this.set_ivar::<CGFloat>("content_inset_right", right);
+        }
     }
 
     extern "C" fn handle_set_right_edge_margin(this: &mut Object, _: Sel, value: CGFloat) {
         unsafe { this.set_ivar::<CGFloat>("right_edge_margin", value) };
     }
 
-    extern "C" fn draw_rect(this: &Object, _: Sel, rect: NSRect) {
-        let arrow_height = unsafe { this.get_ivar::<CGFloat>("arrow_height") };
-
-        let arrow_width = unsafe { this.get_ivar::<CGFloat>("arrow_width") };
-
-        let arrow_position = unsafe { this.get_ivar::<CGFloat>("arrow_position") };
+    extern "C" fn draw_rect(this: &Object, _: Sel, _rect: NSRect) {
+        let bounds: NSRect = unsafe { msg_send![this, bounds] };
 
         let border_width = unsafe { this.get_ivar::<CGFloat>("border_width") };
 
-        let corner_radius = unsafe { this.get_ivar::<CGFloat>("corner_radius") };
-
         let border_color = unsafe { this.get_ivar::<id>("border_color") };
 
         let bg_color = unsafe { this.get_ivar::<id>("background_color") };
 
-        let bg_rect = NSRect::new(
-            rect.origin,
-            NSSize::new(rect.size.width, rect.size.height - arrow_height),
-        );
+        let options = ShapeOptions {
+            arrow_edge: arrow_edge_from_raw(unsafe { *this.get_ivar::<NSInteger>("arrow_edge") }),
+            arrow_height: unsafe { *this.get_ivar::<CGFloat>("arrow_height") },
+            arrow_width: unsafe { *this.get_ivar::<CGFloat>("arrow_width") },
+            arrow_position: unsafe { *this.get_ivar::<CGFloat>("arrow_position") },
+            border_width: *border_width,
+            corner_radius: unsafe { *this.get_ivar::<CGFloat>("corner_radius") },
+        };
 
-        let bg_rect = NSRect::new(
-            NSPoint::new(
-                bg_rect.origin.x + border_width,
-                bg_rect.origin.y + border_width,
-            ),
-            NSSize::new(
-                bg_rect.size.width - 2.0 * border_width,
-                bg_rect.size.height - 2.0 * border_width,
-            ),
+        let path = outline(
+            &Rect::new(0.0, 0.0, bounds.size.width, bounds.size.height),
+            &options,
         );
 
-        let control_points = unsafe { NSBezierPath::new() };
-
-        let window_path = unsafe { NSBezierPath::new() };
-
-        let arrow_path = unsafe { NSBezierPath::new() };
-
-        let bg_path = unsafe { NSBezierPath::new() };
-
-        unsafe {
-            bg_path.appendBezierPathWithRoundedRect_xRadius_yRadius(
-                CGRect::new(
-                    CGPoint::new(bg_rect.origin.x, bg_rect.origin.y),
-                    CGSize::new(bg_rect.size.width, bg_rect.size.height),
-                ),
-                *corner_radius,
-                *corner_radius,
+        // The outline has a top-left origin, and the view a bottom-left one.
+        let to_view = |point: &Point| {
+            CGPoint::new(
+                bounds.origin.x + point.x,
+                bounds.origin.y + bounds.size.height - point.y,
             )
         };
 
-        let x = *arrow_position;
-
-        let y_max = bg_rect.origin.y + bg_rect.size.height;
-
-        let left_point = NSPoint::new(x - arrow_width / 2.0, y_max);
-
-        let to_point = NSPoint::new(x, y_max + arrow_height);
-
-        let right_point = NSPoint::new(x + arrow_width / 2.0, y_max);
-
-        let cp11 = NSPoint::new(x - arrow_width / 6.0, y_max);
-
-        let cp12 = NSPoint::new(x - arrow_width / 9.0, y_max + arrow_height);
-
-        let cp21 = NSPoint::new(x + arrow_width / 9.0, y_max + arrow_height);
-
-        let cp22 = NSPoint::new(x + arrow_width / 6.0, y_max);
-
-        unsafe {
-            control_points.appendBezierPathWithOvalInRect(CGRect::new(
-                CGPoint::new(left_point.x - 2.0, left_point.y - 2.0),
-                CGSize::new(4.0, 4.0),
-            ));
-        }
-
-        unsafe {
-            control_points.appendBezierPathWithOvalInRect(CGRect::new(
-                CGPoint::new(to_point.x - 2.0, to_point.y - 2.0),
-                CGSize::new(4.0, 4.0),
-            ));
-        }
-
-        unsafe {
-            control_points.appendBezierPathWithOvalInRect(CGRect::new(
-                CGPoint::new(right_point.x - 2.0, right_point.y - 2.0),
-                CGSize::new(4.0, 4.0),
-            ));
-        }
-
-        unsafe {
-            control_points.appendBezierPathWithOvalInRect(CGRect::new(
-                CGPoint::new(cp11.x - 2.0, cp11.y - 2.0),
-                CGSize::new(4.0, 4.0),
-            ));
-        }
-
-        unsafe {
-            control_points.appendBezierPathWithOvalInRect(CGRect::new(
-                CGPoint::new(cp12.x - 2.0, cp12.y - 2.0),
-                CGSize::new(4.0, 4.0),
-            ));
-        }
-
-        unsafe {
-            control_points.appendBezierPathWithOvalInRect(CGRect::new(
-                CGPoint::new(cp21.x - 2.0, cp21.y - 2.0),
-                CGSize::new(4.0, 4.0),
-            ));
-        }
-
-        unsafe {
-            control_points.appendBezierPathWithOvalInRect(CGRect::new(
-                CGPoint::new(cp22.x - 2.0, cp22.y - 2.0),
-                CGSize::new(4.0, 4.0),
-            ));
-        }
-
-        unsafe {
-            arrow_path.moveToPoint(CGPoint::new(left_point.x, left_point.y));
-        }
-
-        unsafe {
-            arrow_path.curveToPoint_controlPoint1_controlPoint2(
-                CGPoint::new(to_point.x, to_point.y),
-                CGPoint::new(cp11.x, cp11.y),
-                CGPoint::new(cp12.x, cp12.y),
-            );
-        }
-
-        unsafe {
-            arrow_path.curveToPoint_controlPoint1_controlPoint2(
-                CGPoint::new(right_point.x, right_point.y),
-                CGPoint::new(cp21.x, cp21.y),
-                CGPoint::new(cp22.x, cp22.y),
-            );
-        }
-
-        unsafe {
-            arrow_path.lineToPoint(CGPoint::new(left_point.x, left_point.y));
-        }
-
-        unsafe {
-            arrow_path.closePath();
-        }
+        let window_path = unsafe { NSBezierPath::new() };
 
-        unsafe {
-            window_path.appendBezierPath(&arrow_path);
-            window_path.appendBezierPath(&bg_path);
+        for command in path.commands() {
+            match command {
+                PathCommand::MoveTo(point) => unsafe { window_path.moveToPoint(to_view(point)) },
+                PathCommand::LineTo(point) => unsafe { window_path.lineToPoint(to_view(point)) },
+                PathCommand::CurveTo {
+                    to,
+                    control1,
+                    control2,
+                } => unsafe {
+                    window_path.curveToPoint_controlPoint1_controlPoint2(
+                        to_view(to),
+                        to_view(control1),
+                        to_view(control2),
+                    )
+                },
+                PathCommand::Close => unsafe { window_path.closePath() },
+            }
         }
 
         if !border_color.is_null() {
@@ -365,21 +375,35 @@ impl PopoverView {
         }
     }
 
-    pub fn new(config: PopoverConfig) -> Id<PopoverView> {
+    pub fn new(config: &PopoverConfig) -> Id<PopoverView> {
         let popover_view: id = unsafe { msg_send![Self::class(), alloc] };
 
         let popover_view: id = unsafe { msg_send![popover_view, init] };
 
+        Self::configure(popover_view, config);
+
+        unsafe { Id::from_retained_ptr(popover_view as *mut PopoverView) }
+    }
+
+    /// Sets every field of `config` on the popover view `popover_view`.
+    pub fn configure(popover_view: id, config: &PopoverConfig) {
         let () = unsafe {
             msg_send![popover_view, setPopoverToStatusItemMargin: config.popover_to_status_item_margin ]
         };
 
-        let () = unsafe { msg_send![popover_view, setBackgroundColor: config.background_color] };
+        let background_color = ns_color(&config.background_color);
 
-        let () = unsafe { msg_send![popover_view, setBorderColor: config.border_color] };
+        let () = unsafe { msg_send![popover_view, setBackgroundColor: background_color] };
+
+        let border_color = config.border_color.as_ref().map_or(nil, ns_color);
+
+        let () = unsafe { msg_send![popover_view, setBorderColor: border_color] };
 
         let () = unsafe { msg_send![popover_view, setBorderWidth: config.border_width ] };
 
+        let () =
+            unsafe { msg_send![popover_view, setArrowEdge: arrow_edge_to_raw(config.arrow_edge) ] };
+
         let () = unsafe { msg_send![popover_view, setArrowHeight: config.arrow_height ] };
 
         let () = unsafe { msg_send![popover_view, setArrowWidth: config.arrow_width ] };
@@ -388,12 +412,27 @@ impl PopoverView {
 
         let () = unsafe { msg_send![popover_view, setCornerRadius: config.corner_radius] };
 
-        let () =
-            unsafe { msg_send![popover_view, setContentEdgeInsets: config.content_edge_insets ] };
+        let insets = config.content_edge_insets;
+
+        let () = unsafe {
+            msg_send![popover_view, setContentEdgeInsetsTop: insets.top left: insets.left bottom: insets.bottom right: insets.right]
+        };
 
         let () = unsafe { msg_send![popover_view, setRightEdgeMargin: config.right_edge_margin ] };
+    }
 
-        unsafe { Id::from_retained_ptr(popover_view as *mut PopoverView) }
+    /// Whether `view` is a popover view.
+    fn is_popover(view: id) -> bool {
+        let is_popover: BOOL = unsafe { msg_send![view, isKindOfClass: Self::class()] };
+
+        is_popover == YES
+    }
+
+    /// Finds the popover view `attach` added to `content_view`.
+    pub fn find(content_view: id) -> Option<id> {
+        subviews(content_view)
+            .into_iter()
+            .find(|view| Self::is_popover(*view))
     }
 
     pub fn set_frame(&self, frame: NSRect) {
@@ -422,3 +461,282 @@ impl INSObject for PopoverView {
         Class::get(CLS_NAME).unwrap_or_else(Self::define_class)
     }
 }
+
+fn subviews(view: id) -> Vec<id> {
+    let subviews: id = unsafe { msg_send![view, subviews] };
+
+    let count: usize = unsafe { msg_send![subviews, count] };
+
+    (0..count)
+        .map(|index| -> id { unsafe { msg_send![subviews, objectAtIndex: index] } })
+        .collect()
+}
+
+/// Insets the webview, and any other view beside the popover view in `content_view`, by
+/// `insets`. Their autoresizing keeps these margins as the window resizes.
+fn layout_content(content_view: id, insets: EdgeInsets) {
+    let bounds: NSRect = unsafe { msg_send![content_view, bounds] };
+
+    let flipped: BOOL = unsafe { msg_send![content_view, isFlipped] };
+
+    // Views have a bottom-left origin unless flipped.
+    let y = if flipped == YES {
+        insets.top
+    } else {
+        insets.bottom
+    };
+
+    let frame = NSRect::new(
+        NSPoint::new(bounds.origin.x + insets.left, bounds.origin.y + y),
+        NSSize::new(
+            (bounds.size.width - insets.left - insets.right).max(0.0),
+            (bounds.size.height - insets.top - insets.bottom).max(0.0),
+        ),
+    );
+
+    let autoresizing_mask = NSViewWidthSizable | NSViewHeightSizable;
+
+    for view in subviews(content_view) {
+        if PopoverView::is_popover(view) {
+            continue;
+        }
+
+        let () = unsafe { msg_send![view, setFrame: frame] };
+
+        let () = unsafe { msg_send![view, setAutoresizingMask: autoresizing_mask] };
+    }
+}
+
+/// The `NSWindow` of `window`, or `None` once the window is gone, which callbacks queued on
+/// the main thread may find.
+fn ns_window<R: Runtime>(window: &WebviewWindow<R>) -> Option<id> {
+    window.ns_window().ok().map(|handle| handle as id)
+}
+
+/// The content view of `window`, on the main thread.
+fn content_view<R: Runtime>(window: &WebviewWindow<R>) -> Option<id> {
+    let handle = ns_window(window)?;
+
+    Some(unsafe { msg_send![handle, contentView] })
+}
+
+/// Reports clicks outside `window`, in other apps or in its own app, and Escape presses
+/// on it, to the behavior of its popover. `handle` is the `NSWindow` of `window`. Returns
+/// the monitors, which are retained.
+fn add_event_monitors<R: Runtime>(window: &WebviewWindow<R>, handle: id) -> Vec<id> {
+    let global_window = window.clone();
+
+    let global_handler = ConcreteBlock::new(move |_event: id| {
+        interact(
+            &global_window,
+            Interaction::ClickOutside { within_app: false },
+        );
+    });
+
+    let global_handler = global_handler.copy();
+
+    let local_window = window.clone();
+
+    let local_handler = ConcreteBlock::new(move |event: id| -> id {
+        let event_window: id = unsafe { msg_send![event, window] };
+
+        let event_type: NSUInteger = unsafe { msg_send![event, type] };
+
+        if event_type == NSEventTypeKeyDown {
+            let key_code: u16 = unsafe { msg_send![event, keyCode] };
+
+            if event_window == handle && key_code == ESCAPE_KEY_CODE {
+                interact(&local_window, Interaction::Escape);
+            }
+        } else if event_window != handle {
+            interact(
+                &local_window,
+                Interaction::ClickOutside { within_app: true },
+            );
+        }
+
+        event
+    });
+
+    let local_handler = local_handler.copy();
+
+    unsafe {
+        let global_monitor: id = msg_send![
+            class!(NSEvent),
+            addGlobalMonitorForEventsMatchingMask: CLICK_EVENTS_MASK handler: global_handler
+        ];
+
+        let local_monitor: id = msg_send![
+            class!(NSEvent),
+            addLocalMonitorForEventsMatchingMask: CLICK_EVENTS_MASK | KEY_DOWN_EVENTS_MASK handler: local_handler
+        ];
+
+        [global_monitor, local_monitor]
+            .into_iter()
+            .filter(|monitor| !monitor.is_null())
+            .map(|monitor| msg_send![monitor, retain])
+            .collect()
+    }
+}
+
+fn remove_event_monitors(monitors: Vec<id>) {
+    for monitor in monitors {
+        unsafe {
+            let () = msg_send![class!(NSEvent), removeMonitor: monitor];
+
+            let () = msg_send![monitor, release];
+        }
+    }
+}
+
+/// Adds a popover view drawn with `config` behind the content of `window`, insets the
+/// content to the popover's content rect, and watches for the clicks and key presses its
+/// behavior closes it on.
+pub fn attach<R: Runtime>(window: &WebviewWindow<R>, config: PopoverConfig) -> tauri::Result<()> {
+    let win = window.clone();
+
+    window.run_on_main_thread(move || {
+        let (handle, content_view) = match ns_window(&win).zip(content_view(&win)) {
+            Some(views) => views,
+            None => return,
+        };
+
+        let content_frame: NSRect = unsafe { msg_send![handle, frame] };
+
+        let view = PopoverView::new(&config);
+
+        view.set_frame(NSRect::new(
+            NSPoint::new(0.0, 0.0),
+            NSSize::new(content_frame.size.width, content_frame.size.height),
+        ));
+
+        view.set_parent(content_view);
+
+        view.set_autoresizing();
+
+        layout_content(content_view, config.content_insets());
+
+        let () = unsafe {
+            msg_send![handle, setAnimationBehavior: NSWindowAnimationBehaviorUtilityWindow]
+        };
+
+        let monitors = add_event_monitors(&win, handle);
+
+        // The monitors of a destroyed window with the same label would otherwise keep
+        // reporting clicks to it.
+        let stale = MONITORS.with(|stored| stored.borrow_mut().insert(win.label(), monitors));
+
+        remove_event_monitors(stale.unwrap_or_default());
+    })
+}
+
+/// Redraws the popover view of `window` with `config`, and lays out its content again,
+/// since the arrow may have moved to another edge.
+pub fn apply<R: Runtime>(window: &WebviewWindow<R>, config: PopoverConfig) -> tauri::Result<()> {
+    let win = window.clone();
+
+    window.run_on_main_thread(move || {
+        let content_view = match content_view(&win) {
+            Some(content_view) => content_view,
+            None => return,
+        };
+
+        if let Some(view) = PopoverView::find(content_view) {
+            PopoverView::configure(view, &config);
+
+            let () = unsafe { msg_send![view, setNeedsDisplay: YES] };
+
+            layout_content(content_view, config.content_insets());
+        }
+    })
+}
+
+/// Removes the popover view of `window`, its event monitors and the content's insets.
+pub fn detach<R: Runtime>(window: &WebviewWindow<R>) -> tauri::Result<()> {
+    let win = window.clone();
+
+    window.run_on_main_thread(move || {
+        // The event monitors outlive the window, so they are removed even once it is gone.
+        release(win.label());
+
+        let content_view = match content_view(&win) {
+            Some(content_view) => content_view,
+            None => return,
+        };
+
+        if let Some(view) = PopoverView::find(content_view) {
+            let () = unsafe { msg_send![view, removeFromSuperview] };
+
+            layout_content(content_view, EdgeInsets::default());
+        }
+    })
+}
+
+/// Removes the event monitors of the destroyed window `label`, which hold on to it.
+pub fn release(label: &str) {
+    let monitors = MONITORS.with(|monitors| monitors.borrow_mut().remove(label));
+
+    remove_event_monitors(monitors.unwrap_or_default());
+}
+
+/// Whether the user has asked to reduce motion in the accessibility settings.
+pub fn prefers_reduced_motion() -> bool {
+    let reduce_motion: BOOL = unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+
+        msg_send![workspace, accessibilityDisplayShouldReduceMotion]
+    };
+
+    reduce_motion == YES
+}
+
+/// Draws `frame` of an animation of the popover of `window`, fading the window and
+/// scaling its content around `origin`. Runs on the main thread.
+pub fn render<R: Runtime>(window: &WebviewWindow<R>, frame: &AnimationFrame, origin: Point) {
+    let (handle, view) = match ns_window(window).zip(content_view(window)) {
+        Some(views) => views,
+        None => return,
+    };
+
+    // The window's own animation would play over this one, so it is only kept for when
+    // the window is shown or hidden without one.
+    let behavior = if *frame == AnimationFrame::SHOWN {
+        NSWindowAnimationBehaviorUtilityWindow
+    } else {
+        NSWindowAnimationBehaviorNone
+    };
+
+    let () = unsafe { msg_send![handle, setAnimationBehavior: behavior] };
+
+    let () = unsafe { msg_send![handle, setAlphaValue: frame.opacity as CGFloat] };
+
+    let () = unsafe { msg_send![view, setWantsLayer: YES] };
+
+    let layer: id = unsafe { msg_send![view, layer] };
+
+    if layer.is_null() {
+        return;
+    }
+
+    let bounds: NSRect = unsafe { msg_send![view, bounds] };
+
+    let anchor: NSPoint = unsafe { msg_send![layer, anchorPoint] };
+
+    // Layers transform around their anchor point, and have a bottom-left origin.
+    let x = origin.x - anchor.x * bounds.size.width;
+
+    let y = bounds.size.height - origin.y - anchor.y * bounds.size.height;
+
+    let scale = frame.scale;
+
+    let transform = CGAffineTransform {
+        a: scale,
+        b: 0.0,
+        c: 0.0,
+        d: scale,
+        tx: x * (1.0 - scale),
+        ty: y * (1.0 - scale),
+    };
+
+    let () = unsafe { msg_send![layer, setAffineTransform: transform] };
+}
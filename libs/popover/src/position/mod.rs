@@ -0,0 +1,156 @@
+use monitor::{get_layout, Rect};
+use serde::{Deserialize, Serialize};
+
+use crate::shape::ArrowEdge;
+
+mod tests;
+
+/// The margins and arrow geometry a popover is positioned with. Distances are in the
+/// coordinate space windows are positioned in: logical points on macOS and physical
+/// pixels elsewhere.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PositionOptions {
+    /// The gap between the anchor and the tip of the arrow.
+    pub popover_to_status_item_margin: f64,
+    /// The minimum distance between the window and either side of the visible area.
+    pub right_edge_margin: f64,
+    /// The edge the arrow is on, and so the side of the anchor the window goes: below it
+    /// for `Top` and `None`, above it for `Bottom`, and right or left of it for `Left`
+    /// and `Right`.
+    pub arrow_edge: ArrowEdge,
+    pub arrow_width: f64,
+    pub corner_radius: f64,
+    pub border_width: f64,
+}
+
+impl Default for PositionOptions {
+    fn default() -> Self {
+        Self {
+            popover_to_status_item_margin: 2.0,
+            right_edge_margin: 12.0,
+            arrow_edge: ArrowEdge::Top,
+            arrow_width: 62.0,
+            corner_radius: 12.0,
+            border_width: 2.0,
+        }
+    }
+}
+
+/// Where to put a popover window, and where to draw the arrow.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub x: f64,
+    pub y: f64,
+    /// The edge the arrow is on, with `Auto` resolved.
+    pub arrow_edge: ArrowEdge,
+    /// The position of the arrow's tip along its edge: from the left edge of the window on
+    /// the top and bottom edges, and from the top edge on the left and right ones.
+    pub arrow_position: f64,
+}
+
+/// Places a `width` by `height` window beside `anchor`, on the side its arrow edge
+/// faces, centered on it but kept within `visible_area`, and points the arrow at the
+/// center of `anchor`. The arrow is kept clear of the rounded corners, so it may not
+/// reach an anchor near the edge of the screen.
+pub fn position(
+    anchor: &Rect,
+    width: f64,
+    height: f64,
+    visible_area: &Rect,
+    options: &PositionOptions,
+) -> Placement {
+    let arrow_edge = options.arrow_edge.resolve(anchor, visible_area);
+
+    let (anchor_x, anchor_y) = anchor.center();
+
+    let margin = options.popover_to_status_item_margin;
+
+    let (x, y) = match arrow_edge {
+        ArrowEdge::Left | ArrowEdge::Right => {
+            let x = if arrow_edge == ArrowEdge::Left {
+                anchor.right().max(visible_area.x) + margin
+            } else {
+                anchor.x.min(visible_area.right()) - margin - width
+            };
+
+            (
+                x.min(visible_area.right() - width).max(visible_area.x),
+                centered(
+                    anchor_y,
+                    height,
+                    visible_area.y,
+                    visible_area.bottom(),
+                    options,
+                ),
+            )
+        }
+        _ => {
+            let y = if arrow_edge == ArrowEdge::Bottom {
+                anchor.y.min(visible_area.bottom()) - margin - height
+            } else {
+                anchor.bottom().max(visible_area.y) + margin
+            };
+
+            (
+                centered(
+                    anchor_x,
+                    width,
+                    visible_area.x,
+                    visible_area.right(),
+                    options,
+                ),
+                y.min(visible_area.bottom() - height).max(visible_area.y),
+            )
+        }
+    };
+
+    let (target, length) = if arrow_edge.is_horizontal() {
+        (anchor_x - x, width)
+    } else {
+        (anchor_y - y, height)
+    };
+
+    let inset = options.border_width + options.corner_radius + options.arrow_width / 2.0;
+
+    let arrow_position = if length - inset < inset {
+        length / 2.0
+    } else {
+        target.clamp(inset, length - inset)
+    };
+
+    Placement {
+        x,
+        y,
+        arrow_edge,
+        arrow_position,
+    }
+}
+
+/// Centers a span of `length` on `center`, kept `right_edge_margin` inside `start` and
+/// `end`. Spans longer than that stick to `start`.
+fn centered(center: f64, length: f64, start: f64, end: f64, options: &PositionOptions) -> f64 {
+    (center - length / 2.0)
+        .min(end - options.right_edge_margin - length)
+        .max(start + options.right_edge_margin)
+}
+
+/// The visible area of the monitor under the center of `anchor`, or of the nearest one.
+pub(crate) fn visible_area_for(anchor: &Rect) -> Rect {
+    let (x, y) = anchor.center();
+
+    let layout = get_layout();
+
+    let monitors = layout.monitors();
+
+    monitors
+        .iter()
+        .find(|monitor| monitor.frame.contains(x, y))
+        .or_else(|| {
+            monitors.iter().min_by(|a, b| {
+                a.frame
+                    .distance_to(x, y)
+                    .total_cmp(&b.frame.distance_to(x, y))
+            })
+        })
+        .map_or_else(|| layout.bounds(), |monitor| monitor.visible_frame)
+}
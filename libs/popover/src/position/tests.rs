@@ -0,0 +1,171 @@
+#![cfg(test)]
+
+use monitor::Rect;
+
+use crate::shape::ArrowEdge;
+
+use super::{position, Placement, PositionOptions};
+
+/// A 1440x900 screen with a 25pt menubar.
+fn visible_area() -> Rect {
+    Rect::new(0.0, 25.0, 1440.0, 875.0)
+}
+
+fn tray_icon(x: f64) -> Rect {
+    Rect::new(x, 0.0, 24.0, 24.0)
+}
+
+#[test]
+fn it_centers_the_window_below_the_anchor() {
+    let placement = position(
+        &tray_icon(988.0),
+        300.0,
+        400.0,
+        &visible_area(),
+        &PositionOptions::default(),
+    );
+
+    assert_eq!(
+        placement,
+        Placement {
+            x: 850.0,
+            y: 27.0,
+            arrow_edge: ArrowEdge::Top,
+            arrow_position: 150.0,
+        }
+    );
+}
+
+#[test]
+fn it_keeps_the_window_off_the_screen_edges() {
+    let options = PositionOptions::default();
+
+    // The icon is too close to the right edge to center the window on.
+    let placement = position(&tray_icon(1350.0), 300.0, 400.0, &visible_area(), &options);
+
+    assert_eq!(placement.x, 1440.0 - 12.0 - 300.0);
+    assert_eq!(placement.x + placement.arrow_position, 1350.0 + 12.0);
+
+    let placement = position(&tray_icon(100.0), 300.0, 400.0, &visible_area(), &options);
+
+    assert_eq!(placement.x, 12.0);
+    assert_eq!(placement.x + placement.arrow_position, 112.0);
+}
+
+#[test]
+fn it_keeps_the_arrow_clear_of_the_rounded_corners() {
+    let options = PositionOptions::default();
+
+    // 2pt border, 12pt radius and half of the 62pt arrow.
+    let inset = 2.0 + 12.0 + 31.0;
+
+    let placement = position(&tray_icon(1420.0), 300.0, 400.0, &visible_area(), &options);
+
+    assert_eq!(placement.arrow_position, 300.0 - inset);
+
+    let placement = position(&tray_icon(-10.0), 300.0, 400.0, &visible_area(), &options);
+
+    assert_eq!(placement.arrow_position, inset);
+
+    // Too narrow for the arrow to move at all.
+    let placement = position(&tray_icon(1420.0), 80.0, 400.0, &visible_area(), &options);
+
+    assert_eq!(placement.arrow_position, 40.0);
+}
+
+#[test]
+fn it_positions_on_secondary_monitors() {
+    // A monitor left of the primary one, with no menubar.
+    let visible_area = Rect::new(-1920.0, -200.0, 1920.0, 1080.0);
+
+    let anchor = Rect::new(-960.0, -300.0, 0.0, 0.0);
+
+    let placement = position(
+        &anchor,
+        300.0,
+        400.0,
+        &visible_area,
+        &PositionOptions {
+            popover_to_status_item_margin: 0.0,
+            ..PositionOptions::default()
+        },
+    );
+
+    assert_eq!(
+        placement,
+        Placement {
+            x: -1110.0,
+            y: -200.0,
+            arrow_edge: ArrowEdge::Top,
+            arrow_position: 150.0,
+        }
+    );
+}
+
+#[test]
+fn it_places_the_window_above_a_taskbar_icon() {
+    // A 1920x1080 screen with a 40px taskbar.
+    let visible_area = Rect::new(0.0, 0.0, 1920.0, 1040.0);
+
+    let anchor = Rect::new(1600.0, 1048.0, 24.0, 24.0);
+
+    let options = PositionOptions {
+        arrow_edge: ArrowEdge::Auto,
+        ..PositionOptions::default()
+    };
+
+    let placement = position(&anchor, 300.0, 400.0, &visible_area, &options);
+
+    assert_eq!(
+        placement,
+        Placement {
+            x: 1462.0,
+            y: 1040.0 - 2.0 - 400.0,
+            arrow_edge: ArrowEdge::Bottom,
+            arrow_position: 150.0,
+        }
+    );
+}
+
+#[test]
+fn it_places_the_window_beside_side_anchors() {
+    let options = |arrow_edge| PositionOptions {
+        arrow_edge,
+        ..PositionOptions::default()
+    };
+
+    let anchor = Rect::new(0.0, 488.0, 40.0, 24.0);
+
+    let placement = position(
+        &anchor,
+        300.0,
+        400.0,
+        &visible_area(),
+        &options(ArrowEdge::Left),
+    );
+
+    assert_eq!(
+        placement,
+        Placement {
+            x: 42.0,
+            y: 300.0,
+            arrow_edge: ArrowEdge::Left,
+            arrow_position: 200.0,
+        }
+    );
+
+    let anchor = Rect::new(1400.0, 100.0, 40.0, 24.0);
+
+    let placement = position(
+        &anchor,
+        300.0,
+        400.0,
+        &visible_area(),
+        &options(ArrowEdge::Right),
+    );
+
+    // Kept below the menubar, with the arrow still pointing at the anchor.
+    assert_eq!(placement.x, 1400.0 - 2.0 - 300.0);
+    assert_eq!(placement.y, 25.0 + 12.0);
+    assert_eq!(placement.y + placement.arrow_position, 112.0);
+}
@@ -0,0 +1,590 @@
+use std::fmt::Write;
+
+use monitor::Rect;
+use serde::{Deserialize, Serialize};
+
+mod tests;
+
+/// How far along the tangent a cubic's control points sit to approximate a quarter circle.
+const KAPPA: f64 = 0.552_284_749_831;
+
+/// How far flattened curves may stray from the path.
+const FLATTEN_TOLERANCE: f64 = 0.1;
+
+/// The edge of a popover its arrow is drawn on.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ArrowEdge {
+    #[default]
+    Top,
+    Bottom,
+    Left,
+    Right,
+    /// No arrow.
+    None,
+    /// The edge facing the anchor, picked when the popover is positioned. It is drawn on
+    /// the top edge until then.
+    Auto,
+}
+
+impl ArrowEdge {
+    /// Picks the edge facing `anchor`, from the side of `visible_area` it is closest to,
+    /// so a popover from the menubar points up and one from a bottom taskbar points
+    /// down. Edges other than `Auto` are returned as they are.
+    pub fn resolve(self, anchor: &Rect, visible_area: &Rect) -> Self {
+        if self != Self::Auto {
+            return self;
+        }
+
+        let (x, y) = anchor.center();
+
+        [
+            (Self::Top, y - visible_area.y),
+            (Self::Bottom, visible_area.bottom() - y),
+            (Self::Left, x - visible_area.x),
+            (Self::Right, visible_area.right() - x),
+        ]
+        .iter()
+        .fold((Self::Top, f64::INFINITY), |closest, &(edge, distance)| {
+            if distance < closest.1 {
+                (edge, distance)
+            } else {
+                closest
+            }
+        })
+        .0
+    }
+
+    /// Whether the arrow runs along the top or bottom edge, and its position is horizontal.
+    pub fn is_horizontal(&self) -> bool {
+        !matches!(self, Self::Left | Self::Right)
+    }
+}
+
+/// The arrow and rounded-rect geometry a popover is drawn with.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ShapeOptions {
+    pub arrow_edge: ArrowEdge,
+    pub arrow_height: f64,
+    pub arrow_width: f64,
+    /// The position of the arrow's tip along its edge: from the left edge of the frame on
+    /// the top and bottom edges, and from the top edge on the left and right ones.
+    pub arrow_position: f64,
+    pub border_width: f64,
+    pub corner_radius: f64,
+}
+
+impl Default for ShapeOptions {
+    fn default() -> Self {
+        Self {
+            arrow_edge: ArrowEdge::Top,
+            arrow_height: 12.0,
+            arrow_width: 62.0,
+            arrow_position: 0.0,
+            border_width: 2.0,
+            corner_radius: 12.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+    MoveTo(Point),
+    LineTo(Point),
+    CurveTo {
+        to: Point,
+        control1: Point,
+        control2: Point,
+    },
+    Close,
+}
+
+/// An outline, in a top-left origin coordinate space.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+}
+
+impl Path {
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    /// The path as the `d` attribute of an SVG `<path>`, with coordinates rounded to
+    /// three decimal places.
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+
+        for command in &self.commands {
+            if !svg.is_empty() {
+                svg.push(' ');
+            }
+
+            let _ = match command {
+                PathCommand::MoveTo(point) => write!(svg, "M {}", svg_point(point)),
+                PathCommand::LineTo(point) => write!(svg, "L {}", svg_point(point)),
+                PathCommand::CurveTo {
+                    to,
+                    control1,
+                    control2,
+                } => write!(
+                    svg,
+                    "C {} {} {}",
+                    svg_point(control1),
+                    svg_point(control2),
+                    svg_point(to)
+                ),
+                PathCommand::Close => write!(svg, "Z"),
+            };
+        }
+
+        svg
+    }
+
+    /// The path as polygons, one per subpath, with curves replaced by line segments.
+    pub fn flatten(&self) -> Vec<Vec<Point>> {
+        let mut polygons: Vec<Vec<Point>> = Vec::new();
+
+        for command in &self.commands {
+            match command {
+                PathCommand::MoveTo(point) => polygons.push(vec![*point]),
+                PathCommand::LineTo(point) => match polygons.last_mut() {
+                    Some(polygon) => polygon.push(*point),
+                    None => polygons.push(vec![*point]),
+                },
+                PathCommand::CurveTo {
+                    to,
+                    control1,
+                    control2,
+                } => {
+                    let polygon = match polygons.last_mut() {
+                        Some(polygon) => polygon,
+                        None => continue,
+                    };
+
+                    let from = *polygon.last().unwrap();
+
+                    polygon.extend(flatten_cubic(from, *control1, *control2, *to));
+                }
+                PathCommand::Close => {}
+            }
+        }
+
+        polygons
+    }
+
+    /// The whole pixels inside the path, those whose centers it contains, as one
+    /// rectangle per row, merged with the rows above while they cover the same spans.
+    /// These make up an input shape that follows the path.
+    pub fn pixel_rects(&self) -> Vec<Rect> {
+        let polygons = self.flatten();
+
+        let edges: Vec<(Point, Point)> = polygons
+            .iter()
+            .flat_map(|polygon| {
+                polygon
+                    .iter()
+                    .zip(polygon.iter().cycle().skip(1))
+                    .map(|(from, to)| (*from, *to))
+            })
+            .collect();
+
+        if edges.is_empty() {
+            return Vec::new();
+        }
+
+        let (min_y, max_y) = edges.iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min_y, max_y), (from, _)| (min_y.min(from.y), max_y.max(from.y)),
+        );
+
+        let mut rects: Vec<Rect> = Vec::new();
+
+        let mut previous: Vec<(f64, f64)> = Vec::new();
+
+        let mut open = 0;
+
+        for row in (min_y.floor() as i64)..(max_y.ceil() as i64) {
+            let y = row as f64;
+
+            let spans = row_spans(&edges, y + 0.5);
+
+            if !spans.is_empty() && spans == previous {
+                for rect in &mut rects[open..] {
+                    rect.height += 1.0;
+                }
+
+                continue;
+            }
+
+            open = rects.len();
+
+            rects.extend(spans.iter().map(|(x, width)| Rect::new(*x, y, *width, 1.0)));
+
+            previous = spans;
+        }
+
+        rects
+    }
+
+    fn move_to(&mut self, (x, y): (f64, f64)) {
+        self.commands.push(PathCommand::MoveTo(Point::new(x, y)));
+    }
+
+    fn line_to(&mut self, (x, y): (f64, f64)) {
+        self.commands.push(PathCommand::LineTo(Point::new(x, y)));
+    }
+
+    fn curve_to(&mut self, to: (f64, f64), control1: (f64, f64), control2: (f64, f64)) {
+        self.commands.push(PathCommand::CurveTo {
+            to: Point::new(to.0, to.1),
+            control1: Point::new(control1.0, control1.1),
+            control2: Point::new(control2.0, control2.1),
+        });
+    }
+
+    fn close(&mut self) {
+        self.commands.push(PathCommand::Close);
+    }
+}
+
+/// The points, after `from`, of line segments within `FLATTEN_TOLERANCE` of a cubic.
+fn flatten_cubic(from: Point, control1: Point, control2: Point, to: Point) -> Vec<Point> {
+    // How far the control polygon bends, which bounds how far the segments stray.
+    let bend = ((from.x - 2.0 * control1.x + control2.x)
+        .hypot(from.y - 2.0 * control1.y + control2.y))
+    .max((control1.x - 2.0 * control2.x + to.x).hypot(control1.y - 2.0 * control2.y + to.y));
+
+    let segments = (0.75 * bend / FLATTEN_TOLERANCE)
+        .sqrt()
+        .ceil()
+        .clamp(1.0, 100.0) as usize;
+
+    (1..=segments)
+        .map(|segment| {
+            let t = segment as f64 / segments as f64;
+
+            let u = 1.0 - t;
+
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+
+            Point::new(
+                a * from.x + b * control1.x + c * control2.x + d * to.x,
+                a * from.y + b * control1.y + c * control2.y + d * to.y,
+            )
+        })
+        .collect()
+}
+
+/// The spans of whole pixels, as `(x, width)`, whose centers on the line `y` are inside
+/// `edges`, by the nonzero winding rule.
+fn row_spans(edges: &[(Point, Point)], y: f64) -> Vec<(f64, f64)> {
+    let mut crossings: Vec<(f64, i32)> = edges
+        .iter()
+        .filter_map(|(from, to)| {
+            let direction = if from.y <= y && y < to.y {
+                1
+            } else if to.y <= y && y < from.y {
+                -1
+            } else {
+                return None;
+            };
+
+            let x = from.x + (y - from.y) / (to.y - from.y) * (to.x - from.x);
+
+            Some((x, direction))
+        })
+        .collect();
+
+    crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut spans = Vec::new();
+
+    let mut winding = 0;
+
+    let mut start = 0.0;
+
+    for (x, direction) in crossings {
+        let was_inside = winding != 0;
+
+        winding += direction;
+
+        if !was_inside && winding != 0 {
+            start = x;
+        } else if was_inside && winding == 0 {
+            // Pixels from the first center at or after `start` to the last before `x`.
+            let first = (start - 0.5).ceil();
+
+            let last = (x - 0.5).floor();
+
+            if last >= first {
+                spans.push((first, last - first + 1.0));
+            }
+        }
+    }
+
+    spans
+}
+
+fn svg_number(value: f64) -> f64 {
+    let value = (value * 1000.0).round() / 1000.0;
+
+    // Avoids printing "-0".
+    if value == 0.0 {
+        0.0
+    } else {
+        value
+    }
+}
+
+fn svg_point(point: &Point) -> String {
+    format!("{} {}", svg_number(point.x), svg_number(point.y))
+}
+
+/// Lays out a frame as if its arrow edge were the top one. Points are built as `(u, v)`,
+/// `u` running along the arrow edge and `v` away from it, and then rotated back into the
+/// frame, which keeps clockwise paths clockwise.
+struct Orientation {
+    frame: Rect,
+    edge: ArrowEdge,
+}
+
+impl Orientation {
+    fn new(frame: &Rect, edge: ArrowEdge) -> Self {
+        Self {
+            frame: *frame,
+            edge,
+        }
+    }
+
+    /// The length of the arrow edge.
+    fn length(&self) -> f64 {
+        if self.edge.is_horizontal() {
+            self.frame.width
+        } else {
+            self.frame.height
+        }
+    }
+
+    /// The distance from the arrow edge to the opposite one.
+    fn depth(&self) -> f64 {
+        if self.edge.is_horizontal() {
+            self.frame.height
+        } else {
+            self.frame.width
+        }
+    }
+
+    /// Takes an arrow position, from the frame's left or top edge, to `u`.
+    fn along(&self, position: f64) -> f64 {
+        match self.edge {
+            ArrowEdge::Bottom | ArrowEdge::Left => self.length() - position,
+            _ => position,
+        }
+    }
+
+    fn map(&self, (u, v): (f64, f64)) -> (f64, f64) {
+        let frame = &self.frame;
+
+        match self.edge {
+            ArrowEdge::Bottom => (frame.right() - u, frame.bottom() - v),
+            ArrowEdge::Left => (frame.x + v, frame.bottom() - u),
+            ArrowEdge::Right => (frame.right() - v, frame.y + u),
+            _ => (frame.x + u, frame.y + v),
+        }
+    }
+}
+
+/// The arrow height `options` leave room for, which is none without an arrow.
+pub(crate) fn arrow_height(options: &ShapeOptions) -> f64 {
+    let has_arrow = options.arrow_edge != ArrowEdge::None
+        && options.arrow_width > 0.0
+        && options.arrow_height > 0.0;
+
+    if has_arrow {
+        options.arrow_height
+    } else {
+        0.0
+    }
+}
+
+/// The corner radius the outline is drawn with, shrunk to fit inside the border and
+/// below the arrow.
+fn corner_radius(orientation: &Orientation, options: &ShapeOptions) -> f64 {
+    let length = orientation.length() - 2.0 * options.border_width;
+
+    let depth = orientation.depth() - arrow_height(options) - 2.0 * options.border_width;
+
+    options
+        .corner_radius
+        .min(length / 2.0)
+        .min(depth / 2.0)
+        .max(0.0)
+}
+
+/// Where the arrow's tip is along the arrow edge, as `u`, kept clear of corners of
+/// `radius`.
+fn arrow_center(orientation: &Orientation, options: &ShapeOptions, radius: f64) -> f64 {
+    let half_width = options.arrow_width / 2.0;
+
+    let left = options.border_width;
+
+    let right = orientation.length() - options.border_width;
+
+    let min_x = left + radius + half_width;
+
+    let max_x = right - radius - half_width;
+
+    if max_x < min_x {
+        (left + right) / 2.0
+    } else {
+        orientation
+            .along(options.arrow_position)
+            .clamp(min_x, max_x)
+    }
+}
+
+/// The outline of a popover filling `frame`: a rounded rect inset by the border width,
+/// with the arrow rising from its `arrow_edge` to the edge of the frame. The arrow is
+/// kept clear of the rounded corners, and left out when it has no width or height. The
+/// path runs clockwise, starting at the end of the corner before the arrow.
+pub fn outline(frame: &Rect, options: &ShapeOptions) -> Path {
+    let orientation = Orientation::new(frame, options.arrow_edge);
+
+    let at = |u: f64, v: f64| orientation.map((u, v));
+
+    let border_width = options.border_width;
+
+    let arrow_height = arrow_height(options);
+
+    let left = border_width;
+
+    let right = orientation.length() - border_width;
+
+    let top = arrow_height + border_width;
+
+    let bottom = orientation.depth() - border_width;
+
+    let radius = corner_radius(&orientation, options);
+
+    let handle = radius * (1.0 - KAPPA);
+
+    let mut path = Path::default();
+
+    path.move_to(at(left + radius, top));
+
+    if arrow_height > 0.0 {
+        let half_width = options.arrow_width / 2.0;
+
+        let x = arrow_center(&orientation, options, radius);
+
+        let tip = top - arrow_height;
+
+        path.line_to(at(x - half_width, top));
+
+        path.curve_to(
+            at(x, tip),
+            at(x - options.arrow_width / 6.0, top),
+            at(x - options.arrow_width / 9.0, tip),
+        );
+
+        path.curve_to(
+            at(x + half_width, top),
+            at(x + options.arrow_width / 9.0, tip),
+            at(x + options.arrow_width / 6.0, top),
+        );
+    }
+
+    path.line_to(at(right - radius, top));
+
+    if radius > 0.0 {
+        path.curve_to(
+            at(right, top + radius),
+            at(right - handle, top),
+            at(right, top + handle),
+        );
+    }
+
+    path.line_to(at(right, bottom - radius));
+
+    if radius > 0.0 {
+        path.curve_to(
+            at(right - radius, bottom),
+            at(right, bottom - handle),
+            at(right - handle, bottom),
+        );
+    }
+
+    path.line_to(at(left + radius, bottom));
+
+    if radius > 0.0 {
+        path.curve_to(
+            at(left, bottom - radius),
+            at(left + handle, bottom),
+            at(left, bottom - handle),
+        );
+    }
+
+    path.line_to(at(left, top + radius));
+
+    if radius > 0.0 {
+        path.curve_to(
+            at(left + radius, top),
+            at(left, top + handle),
+            at(left + handle, top),
+        );
+    }
+
+    path.close();
+
+    path
+}
+
+/// The part of `frame` inside the border and clear of the arrow, where content goes.
+pub fn content_rect(frame: &Rect, options: &ShapeOptions) -> Rect {
+    let orientation = Orientation::new(frame, options.arrow_edge);
+
+    let border_width = options.border_width;
+
+    let (x1, y1) = orientation.map((border_width, arrow_height(options) + border_width));
+
+    let (x2, y2) = orientation.map((
+        orientation.length() - border_width,
+        orientation.depth() - border_width,
+    ));
+
+    Rect::new(x1.min(x2), y1.min(y2), (x2 - x1).abs(), (y2 - y1).abs())
+}
+
+/// The tip of the arrow of a popover filling `frame`, or the middle of its arrow edge
+/// when it has no arrow. This is the point a popover grows from as it appears.
+pub fn arrow_tip(frame: &Rect, options: &ShapeOptions) -> Point {
+    let orientation = Orientation::new(frame, options.arrow_edge);
+
+    let (u, v) = if arrow_height(options) > 0.0 {
+        let radius = corner_radius(&orientation, options);
+
+        (
+            arrow_center(&orientation, options, radius),
+            options.border_width,
+        )
+    } else {
+        (orientation.length() / 2.0, 0.0)
+    };
+
+    let (x, y) = orientation.map((u, v));
+
+    Point::new(x, y)
+}
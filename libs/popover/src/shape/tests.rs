@@ -0,0 +1,232 @@
+#![cfg(test)]
+
+use monitor::Rect;
+
+use super::{arrow_tip, content_rect, outline, ArrowEdge, PathCommand, Point, ShapeOptions};
+
+fn frame() -> Rect {
+    Rect::new(0.0, 0.0, 200.0, 100.0)
+}
+
+fn options(arrow_position: f64) -> ShapeOptions {
+    ShapeOptions {
+        arrow_position,
+        ..ShapeOptions::default()
+    }
+}
+
+#[test]
+fn it_draws_the_arrow_above_a_rounded_rect() {
+    let path = outline(&frame(), &options(100.0));
+
+    assert_eq!(
+        path.to_svg(),
+        "M 14 14 L 69 14 C 89.667 14 93.111 2 100 2 C 106.889 2 110.333 14 131 14 L 186 14 C 192.627 14 198 19.373 198 26 L 198 86 C 198 92.627 192.627 98 186 98 L 14 98 C 7.373 98 2 92.627 2 86 L 2 26 C 2 19.373 7.373 14 14 14 Z"
+    );
+}
+
+#[test]
+fn it_keeps_the_arrow_clear_of_the_corners() {
+    let path = outline(&frame(), &options(0.0));
+
+    assert_eq!(
+        path.to_svg(),
+        "M 14 14 L 14 14 C 34.667 14 38.111 2 45 2 C 51.889 2 55.333 14 76 14 L 186 14 C 192.627 14 198 19.373 198 26 L 198 86 C 198 92.627 192.627 98 186 98 L 14 98 C 7.373 98 2 92.627 2 86 L 2 26 C 2 19.373 7.373 14 14 14 Z"
+    );
+}
+
+#[test]
+fn it_leaves_out_an_arrow_without_height() {
+    let options = ShapeOptions {
+        arrow_height: 0.0,
+        corner_radius: 0.0,
+        ..options(100.0)
+    };
+
+    let path = outline(&frame(), &options);
+
+    assert_eq!(path.to_svg(), "M 2 2 L 198 2 L 198 98 L 2 98 L 2 2 Z");
+}
+
+#[test]
+fn it_offsets_the_outline_by_the_frame_origin() {
+    let frame = Rect::new(10.0, 20.0, 200.0, 100.0);
+
+    let path = outline(&frame, &options(100.0));
+
+    assert_eq!(
+        path.commands().first(),
+        Some(&PathCommand::MoveTo(Point::new(24.0, 34.0)))
+    );
+
+    assert_eq!(path.commands().last(), Some(&PathCommand::Close));
+}
+
+fn on_edge(arrow_edge: ArrowEdge, arrow_position: f64) -> ShapeOptions {
+    ShapeOptions {
+        arrow_edge,
+        ..options(arrow_position)
+    }
+}
+
+#[test]
+fn it_draws_the_arrow_on_the_bottom_edge() {
+    let path = outline(&frame(), &on_edge(ArrowEdge::Bottom, 60.0));
+
+    assert_eq!(
+        path.to_svg(),
+        "M 186 86 L 91 86 C 70.333 86 66.889 98 60 98 C 53.111 98 49.667 86 29 86 L 14 86 C 7.373 86 2 80.627 2 74 L 2 14 C 2 7.373 7.373 2 14 2 L 186 2 C 192.627 2 198 7.373 198 14 L 198 74 C 198 80.627 192.627 86 186 86 Z"
+    );
+}
+
+#[test]
+fn it_draws_the_arrow_on_the_side_edges() {
+    let frame = Rect::new(0.0, 0.0, 100.0, 200.0);
+
+    let path = outline(&frame, &on_edge(ArrowEdge::Left, 60.0));
+
+    assert_eq!(
+        path.to_svg(),
+        "M 14 186 L 14 91 C 14 70.333 2 66.889 2 60 C 2 53.111 14 49.667 14 29 L 14 14 C 14 7.373 19.373 2 26 2 L 86 2 C 92.627 2 98 7.373 98 14 L 98 186 C 98 192.627 92.627 198 86 198 L 26 198 C 19.373 198 14 192.627 14 186 Z"
+    );
+
+    let path = outline(&frame, &on_edge(ArrowEdge::Right, 60.0));
+
+    assert_eq!(
+        path.to_svg(),
+        "M 86 14 L 86 29 C 86 49.667 98 53.111 98 60 C 98 66.889 86 70.333 86 91 L 86 186 C 86 192.627 80.627 198 74 198 L 14 198 C 7.373 198 2 192.627 2 186 L 2 14 C 2 7.373 7.373 2 14 2 L 74 2 C 80.627 2 86 7.373 86 14 Z"
+    );
+}
+
+#[test]
+fn it_leaves_out_the_arrow_on_no_edge() {
+    let options = ShapeOptions {
+        corner_radius: 0.0,
+        ..on_edge(ArrowEdge::None, 100.0)
+    };
+
+    let path = outline(&frame(), &options);
+
+    assert_eq!(path.to_svg(), "M 2 2 L 198 2 L 198 98 L 2 98 L 2 2 Z");
+}
+
+#[test]
+fn it_lays_out_the_content_clear_of_the_arrow() {
+    let cases = [
+        (ArrowEdge::Top, Rect::new(2.0, 14.0, 196.0, 84.0)),
+        (ArrowEdge::Bottom, Rect::new(2.0, 2.0, 196.0, 84.0)),
+        (ArrowEdge::Left, Rect::new(14.0, 2.0, 184.0, 96.0)),
+        (ArrowEdge::Right, Rect::new(2.0, 2.0, 184.0, 96.0)),
+        (ArrowEdge::None, Rect::new(2.0, 2.0, 196.0, 96.0)),
+    ];
+
+    for (edge, expected) in cases {
+        assert_eq!(
+            content_rect(&frame(), &on_edge(edge, 100.0)),
+            expected,
+            "{:?}",
+            edge
+        );
+    }
+}
+
+#[test]
+fn it_finds_the_tip_of_the_arrow() {
+    let cases = [
+        (ArrowEdge::Top, 100.0, Point::new(100.0, 2.0)),
+        (ArrowEdge::Bottom, 150.0, Point::new(150.0, 98.0)),
+        (ArrowEdge::Left, 30.0, Point::new(2.0, 45.0)),
+        (ArrowEdge::None, 30.0, Point::new(100.0, 0.0)),
+    ];
+
+    for (edge, arrow_position, expected) in cases {
+        assert_eq!(
+            arrow_tip(&frame(), &on_edge(edge, arrow_position)),
+            expected,
+            "{:?}",
+            edge
+        );
+    }
+}
+
+#[test]
+fn it_points_auto_arrows_at_the_anchor() {
+    let visible_area = Rect::new(0.0, 25.0, 1440.0, 875.0);
+
+    let cases = [
+        // A menubar icon.
+        (Rect::new(1000.0, 0.0, 24.0, 24.0), ArrowEdge::Top),
+        // A taskbar icon.
+        (Rect::new(1000.0, 900.0, 24.0, 40.0), ArrowEdge::Bottom),
+        // Side panels.
+        (Rect::new(0.0, 400.0, 40.0, 24.0), ArrowEdge::Left),
+        (Rect::new(1400.0, 400.0, 40.0, 24.0), ArrowEdge::Right),
+    ];
+
+    for (anchor, expected) in cases {
+        assert_eq!(ArrowEdge::Auto.resolve(&anchor, &visible_area), expected);
+    }
+
+    let anchor = Rect::new(1000.0, 900.0, 24.0, 40.0);
+
+    assert_eq!(
+        ArrowEdge::Top.resolve(&anchor, &visible_area),
+        ArrowEdge::Top
+    );
+}
+
+#[test]
+fn it_flattens_curves_into_polygons() {
+    let path = outline(&frame(), &options(100.0));
+
+    let polygons = path.flatten();
+
+    assert_eq!(polygons.len(), 1);
+
+    let polygon = &polygons[0];
+
+    assert_eq!(polygon.first(), Some(&Point::new(14.0, 14.0)));
+    assert_eq!(polygon.last(), Some(&Point::new(14.0, 14.0)));
+
+    // The tip of the arrow is kept.
+    assert!(polygon.contains(&Point::new(100.0, 2.0)));
+
+    assert!(polygon
+        .iter()
+        .all(|point| point.x >= 2.0 && point.x <= 198.0 && point.y >= 2.0 && point.y <= 98.0));
+}
+
+#[test]
+fn it_covers_the_pixels_inside_the_outline() {
+    let square = ShapeOptions {
+        corner_radius: 0.0,
+        ..on_edge(ArrowEdge::None, 100.0)
+    };
+
+    let rects = outline(&frame(), &square).pixel_rects();
+
+    assert_eq!(rects, vec![Rect::new(2.0, 2.0, 196.0, 96.0)]);
+
+    let rects = outline(&frame(), &options(100.0)).pixel_rects();
+
+    // The arrow's rows are narrow, then the corners round off the body's first rows.
+    let first = rects.first().unwrap();
+
+    assert_eq!(first.y, 2.0);
+    assert!((first.x + first.width / 2.0 - 100.0).abs() <= 1.0);
+    assert!(first.width < 10.0);
+
+    let body = rects
+        .iter()
+        .find(|rect| rect.x == 2.0 && rect.width == 196.0)
+        .unwrap();
+
+    // Rows whose pixel centers are within half a pixel of the body's sides.
+    assert_eq!(body.y, 23.0);
+    assert_eq!(body.bottom(), 89.0);
+
+    let last = rects.last().unwrap();
+
+    assert_eq!(last.bottom(), 98.0);
+    assert!(last.x > 2.0);
+}
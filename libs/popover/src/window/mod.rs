@@ -0,0 +1,791 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use monitor::Rect;
+use tauri::{Emitter, Manager, PhysicalPosition, Runtime, WebviewWindow, WindowEvent};
+use thiserror::Error;
+
+use crate::{
+    animation::{frame, AnimationFrame, AnimationStyle, Easing, PopoverAnimation},
+    behavior::{DismissedEvent, Interaction, PopoverBehavior, DISMISSED_EVENT},
+    config::{EdgeInsets, PopoverColor, PopoverConfig},
+    shape::{arrow_tip, ArrowEdge, Point, ShapeOptions},
+};
+
+mod tests;
+
+/// How often animation frames are drawn.
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How long after a window loses focus to look for the window that took it.
+const FOCUS_SETTLE: Duration = Duration::from_millis(50);
+
+/// A window's popover config, and the edge an `Auto` arrow was last placed on.
+struct StoredPopover {
+    label: String,
+    config: PopoverConfig,
+    resolved_arrow_edge: Option<ArrowEdge>,
+}
+
+impl StoredPopover {
+    /// The config the popover is drawn with: the stored one, with an `Auto` arrow on the
+    /// edge it was placed on.
+    fn drawn(&self) -> PopoverConfig {
+        let arrow_edge = match (self.config.arrow_edge, self.resolved_arrow_edge) {
+            (ArrowEdge::Auto, Some(resolved)) => resolved,
+            (arrow_edge, _) => arrow_edge,
+        };
+
+        PopoverConfig {
+            arrow_edge,
+            ..self.config.clone()
+        }
+    }
+}
+
+/// The popover of each window, by window label.
+static POPOVERS: Mutex<Vec<StoredPopover>> = Mutex::new(Vec::new());
+
+fn store(label: &str, config: PopoverConfig) {
+    let mut popovers = POPOVERS.lock().unwrap();
+
+    match popovers.iter_mut().find(|stored| stored.label == label) {
+        Some(stored) => {
+            stored.config = config;
+
+            stored.resolved_arrow_edge = None;
+        }
+        None => popovers.push(StoredPopover {
+            label: label.to_string(),
+            config,
+            resolved_arrow_edge: None,
+        }),
+    }
+}
+
+/// The config of `label`'s popover, as it was set.
+pub(crate) fn stored(label: &str) -> Option<PopoverConfig> {
+    POPOVERS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|stored| stored.label == label)
+        .map(|stored| stored.config.clone())
+}
+
+/// The config `label`'s popover is drawn with.
+pub(crate) fn drawn(label: &str) -> Option<PopoverConfig> {
+    POPOVERS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|stored| stored.label == label)
+        .map(StoredPopover::drawn)
+}
+
+/// Changes the stored config of `label`'s popover, and returns the config it is now
+/// drawn with.
+fn update(label: &str, change: impl FnOnce(&mut PopoverConfig)) -> Option<PopoverConfig> {
+    let mut popovers = POPOVERS.lock().unwrap();
+
+    let stored = popovers.iter_mut().find(|stored| stored.label == label)?;
+
+    change(&mut stored.config);
+
+    Some(stored.drawn())
+}
+
+/// Records that `label`'s popover was placed with its arrow on `arrow_edge`, which an
+/// `Auto` arrow is drawn on from now on, and returns the config it is now drawn with.
+fn resolve_arrow(label: &str, arrow_edge: ArrowEdge, arrow_position: f64) -> Option<PopoverConfig> {
+    let mut popovers = POPOVERS.lock().unwrap();
+
+    let stored = popovers.iter_mut().find(|stored| stored.label == label)?;
+
+    stored.resolved_arrow_edge = Some(arrow_edge);
+
+    stored.config.arrow_position = arrow_position;
+
+    Some(stored.drawn())
+}
+
+fn take(label: &str) -> Option<PopoverConfig> {
+    let mut popovers = POPOVERS.lock().unwrap();
+
+    let index = popovers.iter().position(|stored| stored.label == label)?;
+
+    Some(popovers.remove(index).config)
+}
+
+/// What a backend keeps about each window it attached a popover to, by window label.
+/// GTK and AppKit objects only live on the main thread, so backends keep it in a thread
+/// local.
+pub(crate) struct Attachments<T>(Vec<(String, T)>);
+
+impl<T> Attachments<T> {
+    pub(crate) fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Keeps `value` for `label`, and returns what was kept for an earlier window with
+    /// the same label, if it was never detached.
+    pub(crate) fn insert(&mut self, label: &str, value: T) -> Option<T> {
+        let previous = self.remove(label);
+
+        self.0.push((label.to_string(), value));
+
+        previous
+    }
+
+    pub(crate) fn remove(&mut self, label: &str) -> Option<T> {
+        let index = self.0.iter().position(|(stored, _)| stored == label)?;
+
+        Some(self.0.remove(index).1)
+    }
+}
+
+/// The animation running on a window's popover, and where the window was placed before
+/// it moved.
+struct RunningAnimation {
+    label: String,
+    id: u64,
+    showing: bool,
+    position: PhysicalPosition<i32>,
+}
+
+static ANIMATIONS: Mutex<Vec<RunningAnimation>> = Mutex::new(Vec::new());
+
+static NEXT_ANIMATION: AtomicU64 = AtomicU64::new(0);
+
+/// Starts an animation of `label`'s popover, taking over from any running one, and
+/// returns its id and where the window was placed. An interrupted animation may have
+/// moved the window from `position`, so the place it started from is kept.
+fn start_animation(
+    label: &str,
+    showing: bool,
+    position: PhysicalPosition<i32>,
+) -> (u64, PhysicalPosition<i32>) {
+    let id = NEXT_ANIMATION.fetch_add(1, Ordering::Relaxed);
+
+    let mut animations = ANIMATIONS.lock().unwrap();
+
+    match animations
+        .iter_mut()
+        .find(|animation| animation.label == label)
+    {
+        Some(animation) => {
+            animation.id = id;
+
+            animation.showing = showing;
+
+            (id, animation.position)
+        }
+        None => {
+            animations.push(RunningAnimation {
+                label: label.to_string(),
+                id,
+                showing,
+                position,
+            });
+
+            (id, position)
+        }
+    }
+}
+
+/// Whether `id` is still the animation running on `label`'s popover.
+fn is_running(label: &str, id: u64) -> bool {
+    ANIMATIONS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|animation| animation.label == label && animation.id == id)
+}
+
+/// Whether `label`'s popover is being animated away.
+fn is_hiding(label: &str) -> bool {
+    ANIMATIONS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|animation| animation.label == label && !animation.showing)
+}
+
+fn finish_animation(label: &str, id: u64) {
+    ANIMATIONS
+        .lock()
+        .unwrap()
+        .retain(|animation| animation.label != label || animation.id != id);
+}
+
+#[derive(Debug, Error)]
+pub enum PopoverError {
+    #[error("The window has no popover")]
+    NotFound,
+    #[error(transparent)]
+    Tauri(#[from] tauri::Error),
+}
+
+/// The popover of a window. Setters redraw the popover with the new value.
+pub struct Popover<R: Runtime> {
+    window: WebviewWindow<R>,
+}
+
+impl<R: Runtime> Popover<R> {
+    pub fn window(&self) -> &WebviewWindow<R> {
+        &self.window
+    }
+
+    /// The popover's current config.
+    pub fn config(&self) -> Result<PopoverConfig, PopoverError> {
+        stored(self.window.label()).ok_or(PopoverError::NotFound)
+    }
+
+    pub fn set_config(&self, config: PopoverConfig) -> Result<(), PopoverError> {
+        self.update(|stored| *stored = config)
+    }
+
+    pub fn set_arrow_edge(&self, arrow_edge: ArrowEdge) -> Result<(), PopoverError> {
+        self.update(|config| config.arrow_edge = arrow_edge)
+    }
+
+    pub fn set_arrow_height(&self, arrow_height: f64) -> Result<(), PopoverError> {
+        self.update(|config| config.arrow_height = arrow_height)
+    }
+
+    pub fn set_arrow_position(&self, arrow_position: f64) -> Result<(), PopoverError> {
+        self.update(|config| config.arrow_position = arrow_position)
+    }
+
+    pub fn set_arrow_width(&self, arrow_width: f64) -> Result<(), PopoverError> {
+        self.update(|config| config.arrow_width = arrow_width)
+    }
+
+    /// Moves the arrow to `arrow_edge`, at `arrow_position` along it, in a single redraw.
+    pub fn set_arrow(
+        &self,
+        arrow_edge: ArrowEdge,
+        arrow_position: f64,
+    ) -> Result<(), PopoverError> {
+        self.update(|config| {
+            config.arrow_edge = arrow_edge;
+
+            config.arrow_position = arrow_position;
+        })
+    }
+
+    /// Draws the arrow on `arrow_edge`, the edge the popover was placed on, at
+    /// `arrow_position` along it. The configured edge is kept, so an `Auto` one is
+    /// resolved again the next time the popover is placed.
+    pub(crate) fn place_arrow(
+        &self,
+        arrow_edge: ArrowEdge,
+        arrow_position: f64,
+    ) -> Result<(), PopoverError> {
+        let config = resolve_arrow(self.window.label(), arrow_edge, arrow_position)
+            .ok_or(PopoverError::NotFound)?;
+
+        apply(&self.window, config)?;
+
+        Ok(())
+    }
+
+    pub fn set_background_color(
+        &self,
+        background_color: impl Into<PopoverColor>,
+    ) -> Result<(), PopoverError> {
+        let background_color = background_color.into();
+
+        self.update(|config| config.background_color = background_color)
+    }
+
+    pub fn set_behavior(&self, behavior: PopoverBehavior) -> Result<(), PopoverError> {
+        self.update(|config| config.behavior = behavior)
+    }
+
+    /// Sets the color of the border, which is not drawn when `None`.
+    pub fn set_border_color(&self, border_color: Option<PopoverColor>) -> Result<(), PopoverError> {
+        self.update(|config| config.border_color = border_color)
+    }
+
+    pub fn set_border_width(&self, border_width: f64) -> Result<(), PopoverError> {
+        self.update(|config| config.border_width = border_width)
+    }
+
+    pub fn set_content_edge_insets(
+        &self,
+        content_edge_insets: EdgeInsets,
+    ) -> Result<(), PopoverError> {
+        self.update(|config| config.content_edge_insets = content_edge_insets)
+    }
+
+    pub fn set_corner_radius(&self, corner_radius: f64) -> Result<(), PopoverError> {
+        self.update(|config| config.corner_radius = corner_radius)
+    }
+
+    pub fn set_popover_to_status_item_margin(
+        &self,
+        popover_to_status_item_margin: f64,
+    ) -> Result<(), PopoverError> {
+        self.update(|config| config.popover_to_status_item_margin = popover_to_status_item_margin)
+    }
+
+    pub fn set_right_edge_margin(&self, right_edge_margin: f64) -> Result<(), PopoverError> {
+        self.update(|config| config.right_edge_margin = right_edge_margin)
+    }
+
+    pub fn set_animation(&self, animation: PopoverAnimation) -> Result<(), PopoverError> {
+        self.update(|config| config.animation = animation)
+    }
+
+    /// Shows the window, with the popover appearing as its `animation` describes. When the
+    /// user has asked the OS to reduce motion, its `reduced_motion_style` is used instead.
+    /// Returns once the animation has started.
+    pub fn show_animated(&self) -> Result<(), PopoverError> {
+        let config = drawn(self.window.label()).ok_or(PopoverError::NotFound)?;
+
+        animate(&self.window, config, true)?;
+
+        Ok(())
+    }
+
+    /// Animates the popover away as its `animation` describes, and then hides the window.
+    /// Returns once the animation has started.
+    pub fn hide_animated(&self) -> Result<(), PopoverError> {
+        let config = drawn(self.window.label()).ok_or(PopoverError::NotFound)?;
+
+        animate(&self.window, config, false)?;
+
+        Ok(())
+    }
+
+    /// Removes the popover from the window.
+    pub fn remove(self) -> Result<(), PopoverError> {
+        take(self.window.label()).ok_or(PopoverError::NotFound)?;
+
+        detach(&self.window)?;
+
+        Ok(())
+    }
+
+    fn update(&self, change: impl FnOnce(&mut PopoverConfig)) -> Result<(), PopoverError> {
+        let config = update(self.window.label(), change).ok_or(PopoverError::NotFound)?;
+
+        apply(&self.window, config)?;
+
+        Ok(())
+    }
+}
+
+pub trait WebviewWindowExt<R: Runtime> {
+    /// Draws a popover behind the window's content, replacing any it has. With no config,
+    /// the default one is used with the arrow centered.
+    fn add_popover(&self, config: Option<PopoverConfig>) -> Result<Popover<R>, PopoverError>;
+
+    /// The window's popover, if it has one.
+    fn popover(&self) -> Option<Popover<R>>;
+}
+
+impl<R: Runtime> WebviewWindowExt<R> for WebviewWindow<R> {
+    fn add_popover(&self, config: Option<PopoverConfig>) -> Result<Popover<R>, PopoverError> {
+        let config = match config {
+            Some(config) => config,
+            None => PopoverConfig {
+                arrow_position: popover_frame(self)?.width / 2.0,
+                ..PopoverConfig::default()
+            },
+        };
+
+        if take(self.label()).is_some() {
+            detach(self)?;
+        }
+
+        store(self.label(), config.clone());
+
+        attach(self, config)?;
+
+        watch_focus(self);
+
+        Ok(Popover {
+            window: self.clone(),
+        })
+    }
+
+    fn popover(&self) -> Option<Popover<R>> {
+        stored(self.label()).map(|_| Popover {
+            window: self.clone(),
+        })
+    }
+}
+
+/// The labels of the windows whose focus is watched. Tauri cannot stop listening to a
+/// window's events, so this is done once per window, until it is destroyed.
+static FOCUS_WATCHED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Drops everything kept about the window `label`, so that a window later created with
+/// the same label starts afresh.
+fn forget(label: &str) {
+    take(label);
+
+    ANIMATIONS
+        .lock()
+        .unwrap()
+        .retain(|animation| animation.label != label);
+
+    FOCUS_WATCHED
+        .lock()
+        .unwrap()
+        .retain(|watched| watched != label);
+}
+
+/// Reports `window` losing focus to the behavior of its popover, along with whether
+/// another of the app's windows took it, and forgets the window once it is destroyed.
+fn watch_focus<R: Runtime>(window: &WebviewWindow<R>) {
+    {
+        let mut watched = FOCUS_WATCHED.lock().unwrap();
+
+        if watched.iter().any(|label| label == window.label()) {
+            return;
+        }
+
+        watched.push(window.label().to_string());
+    }
+
+    let win = window.clone();
+
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            forget(win.label());
+
+            release(win.label());
+
+            return;
+        }
+
+        if !matches!(event, WindowEvent::Focused(false)) {
+            return;
+        }
+
+        let win = win.clone();
+
+        // Focus moves to the next window after this one loses it.
+        thread::spawn(move || {
+            thread::sleep(FOCUS_SETTLE);
+
+            if win.is_focused().unwrap_or(false) {
+                return;
+            }
+
+            let within_app =
+                win.app_handle().webview_windows().values().any(|other| {
+                    other.label() != win.label() && other.is_focused().unwrap_or(false)
+                });
+
+            interact(&win, Interaction::FocusLost { within_app });
+        });
+    });
+}
+
+/// Hides `window`, animating its popover away, and emits `DISMISSED_EVENT` if its
+/// behavior closes it on `interaction`.
+pub(crate) fn interact<R: Runtime>(window: &WebviewWindow<R>, interaction: Interaction) {
+    let config = match drawn(window.label()) {
+        Some(config) => config,
+        None => return,
+    };
+
+    let reason = match config.behavior.dismiss_reason(interaction) {
+        Some(reason) => reason,
+        None => return,
+    };
+
+    // A click outside can also take the focus away, but closes the popover once.
+    if !window.is_visible().unwrap_or(false) || is_hiding(window.label()) {
+        return;
+    }
+
+    if animate(window, config, false).is_err() {
+        let _ = window.hide();
+    }
+
+    let _ = window.emit(
+        DISMISSED_EVENT,
+        DismissedEvent {
+            label: window.label().to_string(),
+            reason,
+        },
+    );
+}
+
+/// The frame the popover of `window` is drawn in, which only macOS draws in logical
+/// points.
+fn popover_frame<R: Runtime>(window: &WebviewWindow<R>) -> tauri::Result<Rect> {
+    let size = window.inner_size()?;
+
+    let (width, height) = if cfg!(target_os = "macos") {
+        let size = size.to_logical::<f64>(window.scale_factor()?);
+
+        (size.width, size.height)
+    } else {
+        (size.width as f64, size.height as f64)
+    };
+
+    Ok(Rect::new(0.0, 0.0, width, height))
+}
+
+/// An animation of a window's popover. Frames are drawn on the main thread.
+struct Animation<R: Runtime> {
+    window: WebviewWindow<R>,
+    id: u64,
+    style: AnimationStyle,
+    easing: Easing,
+    duration: f64,
+    showing: bool,
+    arrow_edge: ArrowEdge,
+    /// Where the window was placed, which slides move it from.
+    position: PhysicalPosition<i32>,
+    /// The tip of the arrow, which the popover scales around.
+    origin: Point,
+}
+
+impl<R: Runtime> Animation<R> {
+    /// Draws `frame`, moving the window from its place when `moved`. Runs on the main
+    /// thread.
+    fn present(&self, frame: &AnimationFrame, moved: bool) {
+        if moved {
+            // Only macOS positions windows in logical points.
+            let scale = if cfg!(target_os = "macos") {
+                self.window.scale_factor().unwrap_or(1.0)
+            } else {
+                1.0
+            };
+
+            let _ = self.window.set_position(PhysicalPosition::new(
+                self.position.x + (frame.offset.x * scale).round() as i32,
+                self.position.y + (frame.offset.y * scale).round() as i32,
+            ));
+        }
+
+        render(&self.window, frame, self.origin);
+    }
+
+    /// Ends the animation with the window shown or hidden, and put back in its place.
+    /// Runs on the main thread.
+    fn finish(&self) {
+        if !self.showing {
+            let _ = self.window.hide();
+        }
+
+        self.present(&AnimationFrame::SHOWN, true);
+
+        finish_animation(self.window.label(), self.id);
+    }
+
+    /// Draws the frames of the animation until it ends or another takes over.
+    fn run(self: Arc<Self>) {
+        let start = Instant::now();
+
+        loop {
+            thread::sleep(FRAME_INTERVAL);
+
+            let time = (start.elapsed().as_secs_f64() / self.duration).min(1.0);
+
+            let eased = self.easing.apply(time);
+
+            let progress = if self.showing { eased } else { 1.0 - eased };
+
+            let frame = frame(self.style, progress, self.arrow_edge);
+
+            let done = time >= 1.0;
+
+            let animation = self.clone();
+
+            let _ = self.window.run_on_main_thread(move || {
+                if !is_running(animation.window.label(), animation.id) {
+                    return;
+                }
+
+                animation.present(&frame, animation.style == AnimationStyle::Slide);
+
+                if done {
+                    animation.finish();
+                }
+            });
+
+            if done || !is_running(self.window.label(), self.id) {
+                return;
+            }
+        }
+    }
+}
+
+/// Shows or hides `window`, animating its popover as `config` describes.
+fn animate<R: Runtime>(
+    window: &WebviewWindow<R>,
+    config: PopoverConfig,
+    showing: bool,
+) -> tauri::Result<()> {
+    let origin = arrow_tip(&popover_frame(window)?, &ShapeOptions::from(&config));
+
+    let (id, position) = start_animation(window.label(), showing, window.outer_position()?);
+
+    let win = window.clone();
+
+    // The reduced motion setting is read, and the window drawn, on the main thread.
+    window.run_on_main_thread(move || {
+        let style = config.animation.style(prefers_reduced_motion());
+
+        let animation = Arc::new(Animation {
+            window: win,
+            id,
+            style,
+            easing: config.animation.easing,
+            duration: config.animation.duration(showing),
+            showing,
+            arrow_edge: config.arrow_edge,
+            position,
+            origin,
+        });
+
+        if style == AnimationStyle::None || animation.duration <= 0.0 {
+            if showing {
+                let _ = animation.window.show();
+            }
+
+            animation.finish();
+
+            return;
+        }
+
+        if showing {
+            animation.present(
+                &frame(style, 0.0, config.arrow_edge),
+                style == AnimationStyle::Slide,
+            );
+
+            let _ = animation.window.show();
+        }
+
+        thread::spawn(move || animation.run());
+    })
+}
+
+fn attach<R: Runtime>(window: &WebviewWindow<R>, config: PopoverConfig) -> tauri::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = (window, config);
+
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::popover::attach(window, config)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::popover::attach(window, config)
+    }
+}
+
+fn apply<R: Runtime>(window: &WebviewWindow<R>, config: PopoverConfig) -> tauri::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = (window, config);
+
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::popover::apply(window, config)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::popover::apply(window, config)
+    }
+}
+
+fn detach<R: Runtime>(window: &WebviewWindow<R>) -> tauri::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = window;
+
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::popover::detach(window)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::popover::detach(window)
+    }
+}
+
+/// Drops what the backend keeps about the destroyed window `label`. Runs on the main
+/// thread, where window events are delivered.
+fn release(label: &str) {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = label;
+
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::popover::release(label)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::popover::release(label)
+    }
+}
+
+fn prefers_reduced_motion() -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::popover::prefers_reduced_motion()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::popover::prefers_reduced_motion()
+    }
+}
+
+fn render<R: Runtime>(window: &WebviewWindow<R>, frame: &AnimationFrame, origin: Point) {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = (window, frame, origin);
+
+        unimplemented!()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux::popover::render(window, frame, origin)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        crate::macos::popover::render(window, frame, origin)
+    }
+}
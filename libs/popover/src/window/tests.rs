@@ -0,0 +1,164 @@
+#![cfg(test)]
+
+use monitor::Rect;
+
+use crate::{
+    config::PopoverConfig,
+    position::{position, PositionOptions},
+    shape::ArrowEdge,
+};
+
+use tauri::PhysicalPosition;
+
+use super::{
+    drawn, finish_animation, forget, is_hiding, is_running, resolve_arrow, start_animation, store,
+    stored, take, update, Attachments, FOCUS_WATCHED,
+};
+
+#[test]
+fn it_keeps_one_config_per_window() {
+    store("first", PopoverConfig::default());
+
+    store(
+        "first",
+        PopoverConfig {
+            corner_radius: 4.0,
+            ..PopoverConfig::default()
+        },
+    );
+
+    store("second", PopoverConfig::default());
+
+    assert_eq!(stored("first").unwrap().corner_radius, 4.0);
+    assert_eq!(stored("second").unwrap().corner_radius, 12.0);
+
+    take("first");
+    take("second");
+}
+
+#[test]
+fn it_updates_stored_configs_only() {
+    store("updated", PopoverConfig::default());
+
+    let config = update("updated", |config| config.arrow_edge = ArrowEdge::Bottom).unwrap();
+
+    assert_eq!(config.arrow_edge, ArrowEdge::Bottom);
+    assert_eq!(stored("updated").unwrap().arrow_edge, ArrowEdge::Bottom);
+
+    assert!(update("missing", |config| config.arrow_edge = ArrowEdge::Bottom).is_none());
+
+    assert!(take("updated").is_some());
+    assert!(take("updated").is_none());
+    assert!(stored("updated").is_none());
+}
+
+#[test]
+fn it_resolves_auto_arrows_each_time_the_popover_is_placed() {
+    store(
+        "placed",
+        PopoverConfig {
+            arrow_edge: ArrowEdge::Auto,
+            ..PopoverConfig::default()
+        },
+    );
+
+    let visible_area = Rect::new(0.0, 0.0, 1920.0, 1080.0);
+
+    let place = |anchor: Rect| {
+        let options = PositionOptions::from(&stored("placed").unwrap());
+
+        let placement = position(&anchor, 300.0, 400.0, &visible_area, &options);
+
+        resolve_arrow("placed", placement.arrow_edge, placement.arrow_position).unwrap()
+    };
+
+    // A tray icon in a top panel, and then in a bottom one.
+    let from_top = place(Rect::new(1800.0, 0.0, 24.0, 24.0));
+
+    assert_eq!(from_top.arrow_edge, ArrowEdge::Top);
+
+    let from_bottom = place(Rect::new(1800.0, 1056.0, 24.0, 24.0));
+
+    assert_eq!(from_bottom.arrow_edge, ArrowEdge::Bottom);
+    assert_eq!(drawn("placed").unwrap().arrow_edge, ArrowEdge::Bottom);
+
+    assert_eq!(stored("placed").unwrap().arrow_edge, ArrowEdge::Auto);
+
+    assert!(from_bottom.content_insets().bottom > from_bottom.content_insets().top);
+
+    take("placed");
+}
+
+#[test]
+fn it_keeps_where_an_interrupted_animation_started() {
+    let (showing, position) = start_animation("animated", true, PhysicalPosition::new(10, 20));
+
+    assert!(!is_hiding("animated"));
+
+    assert_eq!(position, PhysicalPosition::new(10, 20));
+
+    let (hiding, position) = start_animation("animated", false, PhysicalPosition::new(10, 8));
+
+    assert_eq!(position, PhysicalPosition::new(10, 20));
+
+    assert!(is_hiding("animated"));
+
+    assert!(!is_running("animated", showing));
+    assert!(is_running("animated", hiding));
+
+    finish_animation("animated", showing);
+
+    assert!(is_running("animated", hiding));
+
+    finish_animation("animated", hiding);
+
+    let (next, position) = start_animation("animated", true, PhysicalPosition::new(10, 8));
+
+    assert_eq!(position, PhysicalPosition::new(10, 8));
+
+    finish_animation("animated", next);
+}
+
+#[test]
+fn it_forgets_destroyed_windows() {
+    store("destroyed", PopoverConfig::default());
+
+    start_animation("destroyed", true, PhysicalPosition::new(0, 0));
+
+    FOCUS_WATCHED.lock().unwrap().push("destroyed".to_string());
+
+    forget("destroyed");
+
+    assert!(stored("destroyed").is_none());
+    assert!(!is_hiding("destroyed"));
+
+    let (_, position) = start_animation("destroyed", false, PhysicalPosition::new(5, 5));
+
+    assert_eq!(position, PhysicalPosition::new(5, 5));
+    assert!(!FOCUS_WATCHED
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|label| label == "destroyed"));
+
+    forget("destroyed");
+}
+
+#[test]
+fn it_attaches_again_after_a_window_is_destroyed() {
+    let mut attachments = Attachments::new();
+
+    assert!(attachments.insert("recreated", 1).is_none());
+
+    // Destroyed, as `release` does, and created again with the same label.
+    assert_eq!(attachments.remove("recreated"), Some(1));
+
+    assert!(attachments.insert("recreated", 2).is_none());
+
+    // Attached again without being released, the earlier entry is handed back rather
+    // than kept beside the new one.
+    assert_eq!(attachments.insert("recreated", 3), Some(2));
+
+    assert_eq!(attachments.remove("recreated"), Some(3));
+    assert_eq!(attachments.remove("recreated"), None);
+}
@@ -0,0 +1,14 @@
+const COMMANDS: &[&str] = &[
+    "get_menubar_height",
+    "get_menubar",
+    "get_menubars",
+    "get_system_bars",
+    "get_tray_rect",
+    "get_menubar_visibility",
+    "is_menubar_visible",
+    "watch_menubar_visibility",
+];
+
+fn main() {
+    tauri_plugin::Builder::new(COMMANDS).build();
+}
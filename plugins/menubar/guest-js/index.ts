@@ -0,0 +1,98 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+/**
+ * Geometry is in the coordinate space windows are positioned in: a top-left origin,
+ * logical points on macOS and physical pixels elsewhere.
+ */
+export interface Rect {
+	x: number;
+	y: number;
+	width: number;
+	height: number;
+}
+
+export interface Menubar {
+	height: number;
+	monitor_id: number;
+	frame: Rect;
+	auxiliary_top_left_area: Rect | null;
+	auxiliary_top_right_area: Rect | null;
+}
+
+export type SystemBarKind = "Menubar" | "Dock" | "Taskbar" | "Panel";
+
+export type Edge = "Top" | "Bottom" | "Left" | "Right";
+
+export interface SystemBar {
+	kind: SystemBarKind;
+	edge: Edge;
+	thickness: number;
+	monitor_id: number;
+	auto_hide: boolean;
+	rect: Rect;
+}
+
+export interface TrayGeometry {
+	rect: Rect;
+	monitor_id: number | null;
+	/** Whether `rect` is the icon's frame, rather than a square around the pointer. */
+	exact: boolean;
+}
+
+export interface MenubarVisibility {
+	monitor_id: number;
+	visible: boolean;
+	height: number;
+	auto_hide: boolean;
+	fullscreen: boolean;
+}
+
+export type VisibilityChange = "autoHide" | "fullscreen" | "panel";
+
+export interface VisibilityEvent {
+	visibility: MenubarVisibility;
+	change: VisibilityChange;
+}
+
+export const VISIBILITY_CHANGED_EVENT = "menubar://visibility-changed";
+
+/** The menubar height of `monitorId`, or else of the monitor with the cursor. */
+export const getMenubarHeight = async (monitorId?: number) =>
+	invoke<number>("plugin:menubar|get_menubar_height", { monitorId });
+
+export const getMenubar = async (monitorId: number) =>
+	invoke<Menubar | null>("plugin:menubar|get_menubar", { monitorId });
+
+export const getMenubars = async () =>
+	invoke<Menubar[]>("plugin:menubar|get_menubars");
+
+export const getSystemBars = async () =>
+	invoke<SystemBar[]>("plugin:menubar|get_system_bars");
+
+/** Where the tray icon `id` sits, or the app's status item when no id is given. */
+export const getTrayRect = async (id?: string) =>
+	invoke<TrayGeometry | null>("plugin:menubar|get_tray_rect", { id });
+
+export const getMenubarVisibility = async () =>
+	invoke<MenubarVisibility[]>("plugin:menubar|get_menubar_visibility");
+
+export const isMenubarVisible = async (monitorId: number) =>
+	invoke<boolean>("plugin:menubar|is_menubar_visible", { monitorId });
+
+/**
+ * Calls `handler` when a menubar appears or disappears. The plugin starts watching the
+ * menubars when the first handler is registered.
+ */
+export const onVisibilityChanged = async (
+	handler: (event: VisibilityEvent) => void,
+): Promise<UnlistenFn> => {
+	const unlisten = await listen<VisibilityEvent>(
+		VISIBILITY_CHANGED_EVENT,
+		(event) => handler(event.payload),
+	);
+
+	await invoke("plugin:menubar|watch_menubar_visibility");
+
+	return unlisten;
+};
@@ -0,0 +1,109 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use menubar::{Menubar, MenubarVisibility, SystemBar, TrayGeometry, TrayIconExt};
+use tauri::{
+    plugin::{Builder, TauriPlugin},
+    AppHandle, Emitter, Manager, Runtime, State,
+};
+
+/// The event emitted, with a `VisibilityEvent` payload, when a menubar appears or disappears.
+pub const VISIBILITY_CHANGED_EVENT: &str = "menubar://visibility-changed";
+
+/// How often menubar visibility is polled where the OS does not notify us of changes.
+const VISIBILITY_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Whether menubar visibility is being watched for the app.
+#[derive(Default)]
+struct VisibilityWatcher(AtomicBool);
+
+/// The menubar height of the monitor `monitor_id`, or else of the monitor with the cursor.
+#[tauri::command]
+fn get_menubar_height(monitor_id: Option<u32>) -> f64 {
+    let monitor_id = monitor_id.or_else(|| {
+        monitor::get_monitor_with_cursor()
+            .or_else(|| monitor::get_monitors().into_iter().next())
+            .map(|monitor| monitor.id())
+    });
+
+    monitor_id
+        .and_then(menubar::get_menubar)
+        .map_or(0.0, |menubar| menubar.height())
+}
+
+#[tauri::command]
+fn get_menubar(monitor_id: u32) -> Option<Menubar> {
+    menubar::get_menubar(monitor_id)
+}
+
+#[tauri::command]
+fn get_menubars() -> Vec<Menubar> {
+    menubar::get_menubars()
+}
+
+#[tauri::command]
+fn get_system_bars() -> Vec<SystemBar> {
+    menubar::get_system_bars()
+}
+
+/// Where the tray icon `id` sits, or the app's status item when no id is given.
+#[tauri::command]
+fn get_tray_rect<R: Runtime>(app_handle: AppHandle<R>, id: Option<String>) -> Option<TrayGeometry> {
+    match id {
+        Some(id) => app_handle.tray_by_id(id.as_str())?.geometry(),
+        None => menubar::get_status_item(),
+    }
+}
+
+#[tauri::command]
+fn get_menubar_visibility() -> Vec<MenubarVisibility> {
+    menubar::get_menubar_visibility()
+}
+
+#[tauri::command]
+fn is_menubar_visible(monitor_id: u32) -> bool {
+    menubar::is_menubar_visible(monitor_id)
+}
+
+/// Starts emitting `VISIBILITY_CHANGED_EVENT`, unless it already was. `onVisibilityChanged`
+/// calls it, so that menubars are only polled for apps that listen.
+#[tauri::command]
+fn watch_menubar_visibility<R: Runtime>(
+    app_handle: AppHandle<R>,
+    watcher: State<'_, VisibilityWatcher>,
+) {
+    if watcher.0.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    let stream = menubar::watch_menubar_visibility(VISIBILITY_INTERVAL);
+
+    std::thread::spawn(move || {
+        for event in stream {
+            let _ = app_handle.emit(VISIBILITY_CHANGED_EVENT, event);
+        }
+    });
+}
+
+/// Initializes the plugin.
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    Builder::new("menubar")
+        .invoke_handler(tauri::generate_handler![
+            get_menubar_height,
+            get_menubar,
+            get_menubars,
+            get_system_bars,
+            get_tray_rect,
+            get_menubar_visibility,
+            is_menubar_visible,
+            watch_menubar_visibility
+        ])
+        .setup(|app, _api| {
+            app.manage(VisibilityWatcher::default());
+
+            Ok(())
+        })
+        .build()
+}